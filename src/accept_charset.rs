@@ -0,0 +1,46 @@
+use charsets::Charset;
+
+use error::{Error, Result};
+use lexer::Lexer;
+use utils;
+
+/// Parses an `Accept-Charset` header into charsets with their quality values.
+///
+/// Entries without `q` parameter have the quality 1. The wildcard `*` is returned as
+/// `Charset::Unregistered("*")`. Fails with `Error::InvalidParameter` if a quality value is
+/// malformed and with `Error::Invalid` if any entry is malformed otherwise or has a parameter
+/// other than `q`.
+///
+/// ```
+/// use media_types::{parse_accept_charset, Charset};
+///
+/// assert_eq!(parse_accept_charset("utf-8, iso-8859-1;q=0.5").unwrap(),
+///            vec![(Charset::Utf8, 1.0), (Charset::Iso88591, 0.5)]);
+/// ```
+pub fn parse_accept_charset(s: &str) -> Result<Vec<(Charset, f32)>> {
+    utils::split_list(s).into_iter().map(parse_entry).collect()
+}
+
+fn parse_entry(entry: &str) -> Result<(Charset, f32)> {
+    let mut lexer = Lexer::new(entry.as_bytes());
+    let charset: Charset = lexer.token()?.parse()?;
+    let mut quality = 1.0;
+    lexer.skip_whitespace();
+    if lexer.peek() == Some(b';') {
+        lexer.expect(b';')?;
+        lexer.skip_whitespace();
+        if !lexer.token()?.eq_ignore_ascii_case("q") {
+            return Err(Error::Invalid);
+        }
+        lexer.skip_whitespace();
+        lexer.expect(b'=')?;
+        lexer.skip_whitespace();
+        quality = utils::parse_qvalue(lexer.token()?)
+                      .map_err(|_| Error::InvalidParameter("q".to_owned()))?;
+        lexer.skip_whitespace();
+    }
+    if !lexer.is_eof() {
+        return Err(Error::Invalid);
+    }
+    Ok((charset, quality))
+}
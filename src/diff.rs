@@ -0,0 +1,82 @@
+use std::fmt::{self, Display, Formatter};
+
+use MediaType;
+
+/// The differences between two media types as returned by `MediaType::diff()`.
+///
+/// Parameters are listed sorted by name, values are compared exactly like `==` does.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MediaTypeDiff {
+    /// The top-level types differ.
+    pub type_differs: bool,
+    /// The subtypes differ in their tree, name or suffix.
+    pub subtype_differs: bool,
+    /// Parameters only present in the other media type as name and value.
+    pub added: Vec<(String, String)>,
+    /// Parameters only present in this media type as name and value.
+    pub removed: Vec<(String, String)>,
+    /// Parameters present in both with a different value as name, old and new value.
+    pub changed: Vec<(String, String, String)>,
+}
+
+impl MediaTypeDiff {
+    /// Checks if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        !self.type_differs && !self.subtype_differs && self.added.is_empty() &&
+        self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl MediaType {
+    /// Describes how the other media type differs from this one.
+    pub fn diff(&self, other: &MediaType) -> MediaTypeDiff {
+        let mut diff = MediaTypeDiff {
+            type_differs: self.type_ != other.type_,
+            subtype_differs: self.subtype != other.subtype,
+            ..Default::default()
+        };
+        for (key, value) in &self.parameters {
+            match other.parameters.get(key) {
+                None => diff.removed.push((key.to_string(), value.to_string())),
+                Some(new) if new != value => {
+                    diff.changed.push((key.to_string(), value.to_string(), new.to_string()))
+                }
+                Some(_) => (),
+            }
+        }
+        for (key, value) in &other.parameters {
+            if !self.parameters.contains_key(key) {
+                diff.added.push((key.to_string(), value.to_string()));
+            }
+        }
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort();
+        diff
+    }
+}
+
+impl Display for MediaTypeDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no differences");
+        }
+        let mut parts = Vec::new();
+        if self.type_differs {
+            parts.push("type differs".to_owned());
+        }
+        if self.subtype_differs {
+            parts.push("subtype differs".to_owned());
+        }
+        for (key, value) in &self.added {
+            parts.push(format!("added parameter {}={:?}", key, value));
+        }
+        for (key, value) in &self.removed {
+            parts.push(format!("removed parameter {}={:?}", key, value));
+        }
+        for (key, old, new) in &self.changed {
+            parts.push(format!("changed parameter {} from {:?} to {:?}", key, old, new));
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
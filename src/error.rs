@@ -6,11 +6,24 @@ use std::string::FromUtf8Error;
 use charsets;
 
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 /// Defines an Error type for media types.
 pub enum Error {
-    /// Parsing the given string as a media type failed.
+    /// Parsing the given string as a media type failed. This is the catch-all for failures
+    /// without a more specific variant.
     Invalid,
+    /// The slash separating type and subtype is missing.
+    MissingSeparator,
+    /// The top-level type is malformed.
+    InvalidType,
+    /// The subtype is empty or malformed.
+    InvalidSubtype,
+    /// The value of the named parameter is invalid.
+    InvalidParameter(String),
+    /// A type, subtype or parameter name is longer than 127 characters.
+    NameTooLong,
+    /// The media type has more parameters than allowed by `ParseOptions::max_parameters`.
+    TooManyParameters,
     /// The media type does not have this parameter.
     NotFound,
     /// Decoding a string as UTF-8 (or ASCII) failed.
@@ -18,15 +31,7 @@ pub enum Error {
 }
 
 impl error::Error for Error {
-    fn description(&self) -> &str {
-        match *self {
-            Error::Invalid => "given media type is invalid",
-            Error::NotFound => "given parameter not found",
-            Error::Utf8Error(_) => "decoding as UTF-8 failed",
-        }
-    }
-
-    fn cause(&self) -> Option<&error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         if let Error::Utf8Error(ref error) = *self {
             Some(error)
         } else {
@@ -37,7 +42,19 @@ impl error::Error for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(error::Error::description(self))
+        f.write_str(match *self {
+            Error::Invalid => "given media type is invalid",
+            Error::MissingSeparator => "slash between type and subtype is missing",
+            Error::InvalidType => "type is invalid",
+            Error::InvalidSubtype => "subtype is invalid",
+            Error::InvalidParameter(ref name) => {
+                return write!(f, "value of parameter `{}` is invalid", name);
+            }
+            Error::NameTooLong => "name is too long",
+            Error::TooManyParameters => "too many parameters",
+            Error::NotFound => "given parameter not found",
+            Error::Utf8Error(_) => "decoding as UTF-8 failed",
+        })
     }
 }
 
@@ -0,0 +1,54 @@
+//! Character classes and rules of the media type grammar.
+//!
+//! These are the same rules the parser uses, they can be used to validate user input or to
+//! build parsers for related header fields.
+
+use utils;
+
+/// Checks if the character may appear in a token like a type, subtype or parameter name.
+///
+/// `tchar = "!" / "#" / "$" / "%" / "&" / "'" / "*" / "+" / "-" / "." / "^" / "_" / "`"
+/// / "|" / "~" / DIGIT / ALPHA`
+pub fn is_token_char(c: char) -> bool {
+    utils::tchar(c)
+}
+
+/// Checks if the character may appear in a multipart boundary.
+///
+/// `bchars := DIGIT / ALPHA / "'" / "(" / ")" / "+" / "_" / "," / "-" / "." / "/" / ":"
+/// / "=" / "?" / " "`
+pub fn is_boundary_char(c: char) -> bool {
+    utils::bchars(c)
+}
+
+/// Checks if the byte is whitespace skipped between the parts of a media type: space,
+/// horizontal tab, carriage return or line feed.
+pub fn is_http_whitespace(c: u8) -> bool {
+    utils::is_whitespace(c)
+}
+
+/// Checks if the string is a non-empty token.
+pub fn is_token(s: &str) -> bool {
+    utils::token(s)
+}
+
+/// Checks if the string is a valid multipart boundary of at most 70 characters that does not
+/// end with a space.
+pub fn is_boundary(s: &str) -> bool {
+    utils::boundary(s)
+}
+
+/// Checks if the value of the named parameter is written as quoted string by `Display`.
+///
+/// Values that are not tokens are quoted, this includes the empty string. Values of
+/// extended parameters like `title*` are written unquoted if they are valid RFC 2231
+/// extended values. Bare flags without value are not covered, they are written without `=`.
+pub fn value_needs_quoting(name: &str, value: &str) -> bool {
+    utils::needs_quoting(name, value)
+}
+
+/// Checks if a parameter value can be represented at all, values containing CR, LF or NUL
+/// cannot be written even as quoted string.
+pub fn is_representable_value(value: &str) -> bool {
+    utils::representable_value(value)
+}
@@ -0,0 +1,272 @@
+//! Primitives for parsing structured header fields.
+//!
+//! The media type parser is built on these, they can be reused to parse similar header
+//! fields like `Accept-Charset` or `Content-Disposition`.
+
+use std::ops::Range;
+use std::str;
+
+use error::{Error, Result};
+use utils;
+
+/// Reads tokens and quoted strings from a byte sequence while tracking the current position.
+#[derive(Clone, Debug)]
+pub struct Lexer<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    /// Creates a lexer positioned at the start of the input.
+    pub fn new(input: &'a [u8]) -> Lexer<'a> {
+        Lexer::at(input, 0)
+    }
+
+    /// Creates a lexer positioned at the given byte offset of the input.
+    pub fn at(input: &'a [u8], position: usize) -> Lexer<'a> {
+        Lexer { input, position }
+    }
+
+    /// Returns the byte offset of the next unread byte.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Checks if the whole input has been read.
+    pub fn is_eof(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    /// Returns the next byte without consuming it.
+    pub fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).cloned()
+    }
+
+    /// Returns the input that has not been read yet.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.input[self.position.min(self.input.len())..]
+    }
+
+    /// Skips spaces, tabs and line breaks.
+    pub fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !utils::is_whitespace(c) {
+                break;
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Consumes the given byte or fails with `Error::Invalid` if the next byte is different.
+    pub fn expect(&mut self, expected: u8) -> Result<()> {
+        if self.peek() != Some(expected) {
+            return Err(Error::Invalid);
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Reads a `token = 1*tchar`, fails with `Error::Invalid` if there is no token.
+    pub fn token(&mut self) -> Result<&'a str> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if !utils::tchar(c as char) {
+                break;
+            }
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(Error::Invalid);
+        }
+        str::from_utf8(&self.input[start..self.position]).map_err(|_| Error::Invalid)
+    }
+
+    /// Reads a quoted string and returns its content with quoted-pairs unescaped.
+    ///
+    /// Fails with `Error::Invalid` if the next byte is not a double quote. Like the
+    /// [MIME Sniffing standard](https://mimesniff.spec.whatwg.org/#parsing-a-mime-type) a
+    /// missing closing quote ends the string at the end of input and a trailing backslash is
+    /// kept literally.
+    pub fn quoted_string(&mut self) -> Result<Vec<u8>> {
+        self.expect(b'"')?;
+        let mut value = Vec::new();
+        while let Some(c) = self.peek() {
+            self.position += 1;
+            match c {
+                b'"' => return Ok(value),
+                b'\\' => {
+                    match self.peek() {
+                        Some(escaped) => {
+                            value.push(escaped);
+                            self.position += 1;
+                        }
+                        None => value.push(c),
+                    }
+                }
+                _ => value.push(c),
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// A piece of a media type with its byte span in the input, as returned by `tokenize()`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Token {
+    /// The top-level type.
+    Type(Range<usize>),
+    /// The registration tree facet like `vnd` without the dot.
+    Subtree(Range<usize>),
+    /// The subtype name without tree and suffix.
+    Subtype(Range<usize>),
+    /// The structured syntax suffix without the plus sign.
+    Suffix(Range<usize>),
+    /// The name of a parameter.
+    ParamName(Range<usize>),
+    /// The value of a parameter, quoted strings include their quotes. The span is empty for
+    /// an empty value like in `charset=`.
+    ParamValue(Range<usize>),
+    /// One of the delimiters `/`, `.`, `+`, `;` or `=`.
+    Separator(Range<usize>),
+}
+
+impl Token {
+    /// Returns the byte span of the token in the input.
+    pub fn span(&self) -> Range<usize> {
+        match *self {
+            Token::Type(ref span) |
+            Token::Subtree(ref span) |
+            Token::Subtype(ref span) |
+            Token::Suffix(ref span) |
+            Token::ParamName(ref span) |
+            Token::ParamValue(ref span) |
+            Token::Separator(ref span) => span.clone(),
+        }
+    }
+}
+
+/// Splits a media type into tokens in source order without building a `MediaType`.
+///
+/// Accepts the same input as `MediaType::from_str()`: whitespace around `=`, bare flags
+/// without value and empty values are allowed, a bare flag is a `ParamName` without
+/// `ParamValue`. Whitespace and other bytes the parser ignores are skipped. The sequence
+/// ends with an error item where the parser fails.
+pub fn tokenize(input: &str) -> Vec<Result<Token>> {
+    let mut tokens = Vec::new();
+    if let Err(err) = tokenize_into(input.as_bytes(), &mut tokens) {
+        tokens.push(Err(err));
+    }
+    tokens
+}
+
+fn tokenize_into(input: &[u8], tokens: &mut Vec<Result<Token>>) -> Result<()> {
+    let mut lexer = Lexer::new(input);
+    lexer.skip_whitespace();
+    let type_ = take_until(&mut lexer, |c| c == b'/');
+    if lexer.is_eof() {
+        return Err(Error::MissingSeparator);
+    }
+    if !input[type_.clone()].is_ascii() {
+        return Err(Error::InvalidType);
+    }
+    if type_.len() > 127 {
+        return Err(Error::NameTooLong);
+    }
+    tokens.push(Ok(Token::Type(type_)));
+    separator(&mut lexer, b'/', tokens)?;
+    let start = lexer.position();
+    let subtype = take_until(&mut lexer, |c| utils::is_whitespace(c) || c == b';');
+    if subtype.is_empty() || !input[subtype.clone()].is_ascii() {
+        return Err(Error::InvalidSubtype);
+    }
+    if subtype.len() > 127 {
+        return Err(Error::NameTooLong);
+    }
+    let subtype = str::from_utf8(&input[subtype]).map_err(|_| Error::InvalidSubtype)?;
+    let (prefix, suffix) = match subtype.rfind('+') {
+        Some(i) => (start..start + i, Some(start + i)),
+        None => (start..lexer.position(), None),
+    };
+    if prefix.is_empty() || suffix.is_some_and(|plus| plus + 1 == lexer.position()) {
+        return Err(Error::InvalidSubtype);
+    }
+    match subtype[..prefix.end - start].find('.') {
+        Some(i) => {
+            let sub = &subtype[i + 1..prefix.end - start];
+            if i == 0 || sub.split('.').any(str::is_empty) {
+                return Err(Error::InvalidSubtype);
+            }
+            tokens.push(Ok(Token::Subtree(start..start + i)));
+            tokens.push(Ok(Token::Separator(start + i..start + i + 1)));
+            tokens.push(Ok(Token::Subtype(start + i + 1..prefix.end)));
+        }
+        None => tokens.push(Ok(Token::Subtype(prefix))),
+    }
+    if let Some(plus) = suffix {
+        tokens.push(Ok(Token::Separator(plus..plus + 1)));
+        tokens.push(Ok(Token::Suffix(plus + 1..lexer.position())));
+    }
+    let mut count = 0;
+    loop {
+        // Like the parser skip everything up to the next semicolon.
+        while let Some(c) = lexer.peek() {
+            match c {
+                b';' => break,
+                b'"' => {
+                    lexer.quoted_string()?;
+                }
+                _ => lexer.position += 1,
+            }
+        }
+        if lexer.is_eof() {
+            return Ok(());
+        }
+        separator(&mut lexer, b';', tokens)?;
+        lexer.skip_whitespace();
+        count += 1;
+        if count > 128 {
+            return Err(Error::TooManyParameters);
+        }
+        let name = take_until(&mut lexer, |c| utils::is_whitespace(c) || c == b'=' || c == b';');
+        if name.len() > 128 {
+            return Err(Error::NameTooLong);
+        }
+        if !name.is_empty() {
+            tokens.push(Ok(Token::ParamName(name)));
+        }
+        lexer.skip_whitespace();
+        if lexer.peek() != Some(b'=') {
+            continue;
+        }
+        separator(&mut lexer, b'=', tokens)?;
+        lexer.skip_whitespace();
+        let start = lexer.position();
+        if lexer.peek() == Some(b'"') {
+            lexer.quoted_string()?;
+        } else {
+            take_until(&mut lexer, |c| utils::is_whitespace(c) || c == b';');
+        }
+        tokens.push(Ok(Token::ParamValue(start..lexer.position())));
+    }
+}
+
+/// Consumes bytes up to the first one matching `stop` and returns their span.
+fn take_until<F>(lexer: &mut Lexer, stop: F) -> Range<usize>
+    where F: Fn(u8) -> bool
+{
+    let start = lexer.position();
+    while let Some(c) = lexer.peek() {
+        if stop(c) {
+            break;
+        }
+        lexer.position += 1;
+    }
+    start..lexer.position()
+}
+
+fn separator(lexer: &mut Lexer, delimiter: u8, tokens: &mut Vec<Result<Token>>) -> Result<()> {
+    let start = lexer.position();
+    lexer.expect(delimiter)?;
+    tokens.push(Ok(Token::Separator(start..lexer.position())));
+    Ok(())
+}
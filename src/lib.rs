@@ -21,20 +21,47 @@ extern crate charsets;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
-use std::fmt::{self, Display, Formatter};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter, Write};
+use std::io;
 use std::str::FromStr;
 
 pub use charsets::Charset;
 
 pub use self::Type::{Application, Audio, Image, Message, Model, Multipart, Text, Video};
 pub use self::Tree::{Personal, Private, Standards, Vendor};
+pub use accept_charset::parse_accept_charset;
+pub use diff::MediaTypeDiff;
 pub use error::{Error, Result};
+pub use list::MediaTypeList;
+pub use matcher::MimeMatcher;
+pub use normalized::NormalizedMediaType;
+pub use options::ParseOptions;
+pub use shared::SharedMediaType;
+#[doc(hidden)]
+pub use macros::is_valid_literal as __is_valid_media_type_literal;
 
+pub mod grammar;
+pub mod lexer;
+pub mod registry;
+
+mod accept_charset;
+mod diff;
 mod error;
+mod list;
+#[macro_use]
+mod macros;
+mod matcher;
+mod normalized;
+mod options;
+mod shared;
 mod utils;
 
 /// A Media Type commonly used to describe the contents of a resource.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+///
+/// `Debug` prints the media type like `MediaType { text/html; charset=utf-8 }`, the alternate
+/// form `{:#?}` prints all fields.
+#[derive(Clone, Default, Eq, PartialEq)]
 pub struct MediaType {
     /// The top-level type or `None` to match all types.
     pub type_: Option<Type>,
@@ -45,11 +72,17 @@ pub struct MediaType {
     /// common values are "xml" and "json".
     pub subtype: Option<(Tree, Cow<'static, str>, Option<Cow<'static, str>>)>,
     /// Media types can contain optional parameters for example for charsets or video codes.
+    /// An empty map does not allocate, so parameter-less media types are cheap to create
+    /// and clone.
+    ///
+    /// Parameters given without `=` like `foo` in `text/plain; foo` are stored with the value
+    /// `Cow::Borrowed("")` and written back without `=`. Other empty values like the ones
+    /// parsed from `foo=` or set with `set_parameter()` are written as `foo=""`.
     pub parameters: HashMap<Cow<'static, str>, Cow<'static, str>>,
 }
 
 /// Provides the six discrete and the two composite top-level media types.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Type {
     /// The "text" top-level type is intended for sending material that is
     /// principally textual in form.
@@ -78,9 +111,10 @@ pub enum Type {
     Unregistered(Cow<'static, str>),
 }
 
-impl Display for Type {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.write_str(match *self {
+impl Type {
+    /// Returns the name of the top-level type.
+    pub fn as_str(&self) -> &str {
+        match *self {
             Text => "text",
             Image => "image",
             Audio => "audio",
@@ -90,12 +124,92 @@ impl Display for Type {
             Message => "message",
             Model => "model",
             Type::Unregistered(ref string) => &string[..],
-        })
+        }
+    }
+
+    /// Checks if this is a composite type containing other entities, these are
+    /// `multipart` and `message`.
+    pub fn is_composite(&self) -> bool {
+        *self == Multipart || *self == Message
+    }
+
+    /// Checks if this is a discrete type, this is the case for all types that are not
+    /// composite. Unregistered types are considered discrete.
+    pub fn is_discrete(&self) -> bool {
+        !self.is_composite()
+    }
+
+    /// Compares the type names ignoring ASCII case.
+    ///
+    /// The derived `PartialEq` compares unregistered names exactly, so
+    /// `Unregistered("Foo")` and `Unregistered("foo")` are only equal using this method.
+    pub fn eq_ignore_ascii_case(&self, other: &Type) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+
+    /// Checks if this is `audio` or `video`. Images are not considered audiovisual.
+    pub fn is_audiovisual(&self) -> bool {
+        *self == Audio || *self == Video
+    }
+
+    /// Classifies a discrete type, returns `None` for composite types.
+    pub fn discrete_kind(&self) -> Option<DiscreteKind> {
+        match *self {
+            Text => Some(DiscreteKind::Text),
+            Image => Some(DiscreteKind::Image),
+            Audio => Some(DiscreteKind::Audio),
+            Video => Some(DiscreteKind::Video),
+            Application => Some(DiscreteKind::Application),
+            Model => Some(DiscreteKind::Model),
+            Multipart | Message => None,
+            Type::Unregistered(_) => Some(DiscreteKind::Other),
+        }
+    }
+}
+
+/// The kind of a discrete top-level type as returned by `Type::discrete_kind()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DiscreteKind {
+    /// The `text` type.
+    Text,
+    /// The `image` type.
+    Image,
+    /// The `audio` type.
+    Audio,
+    /// The `video` type.
+    Video,
+    /// The `application` type.
+    Application,
+    /// The `model` type.
+    Model,
+    /// Any unregistered top-level type.
+    Other,
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Compares the name of the type case-insensitively.
+impl PartialEq<str> for Type {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Type {
+    fn eq(&self, other: &&'a str) -> bool {
+        *self == **other
     }
 }
 
 /// Provides the four registration trees.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// Trees are ordered standards first, then vendor, personal, private and finally unregistered
+/// trees sorted by name.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Tree {
     /// The standards tree is intended for types of general interest to the Internet community.
     Standards,
@@ -124,6 +238,32 @@ impl Display for Tree {
     }
 }
 
+/// Content transfer encodings used to send entities over 7bit transports like email.
+///
+/// They are defined in [RFC2045 - Multipurpose Internet Mail Extensions (MIME) Part One:
+/// Format of Internet Message Bodies #6. Content-Transfer-Encoding Header Field]
+/// (https://tools.ietf.org/html/rfc2045#section-6).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferEncoding {
+    /// The data consists of short lines of US-ASCII characters and needs no encoding.
+    SevenBit,
+    /// Suitable for mostly textual data, printable characters stay readable.
+    QuotedPrintable,
+    /// Suitable for arbitrary binary data.
+    Base64,
+}
+
+/// The state of the charset parameter as returned by `MediaType::charset_state()`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CharsetState {
+    /// There is no charset parameter.
+    Absent,
+    /// The charset is registered and known to the `charsets` crate.
+    Known(Charset),
+    /// The charset parameter is present but its value is empty or unknown.
+    Unknown(String),
+}
+
 impl MediaType {
     /// Creates the wildcard media type `*/*`.
     pub fn wildcard() -> MediaType {
@@ -166,6 +306,71 @@ impl MediaType {
         }
     }
 
+    /// Attaches a suffix to the subtype, replacing any existing suffix.
+    ///
+    /// Media types without subtype like `image/*` are returned unchanged. Like `new()` the
+    /// suffix is not validated, use `set_suffix()` for untrusted input.
+    pub fn with_suffix<S>(mut self, suffix: S) -> MediaType
+        where S: Into<Cow<'static, str>>
+    {
+        if let Some(ref mut subtype) = self.subtype {
+            subtype.2 = Some(suffix.into());
+        }
+        self
+    }
+
+    /// Returns a copy with the suffix removed, `image/svg+xml` becomes `image/svg`.
+    ///
+    /// Parameters are kept.
+    pub fn without_suffix(&self) -> MediaType {
+        let mut media_type = self.clone();
+        if let Some(ref mut subtype) = media_type.subtype {
+            subtype.2 = None;
+        }
+        media_type
+    }
+
+    /// Creates a `text/*` media type with the given subtype and charset.
+    ///
+    /// ```
+    /// use media_types::{Charset, MediaType};
+    ///
+    /// assert_eq!(MediaType::text("html", Charset::Utf8).to_string(),
+    ///            "text/html; charset=utf-8");
+    /// ```
+    pub fn text(subtype: &'static str, charset: Charset) -> MediaType {
+        let mut media_type = MediaType::new(Text, Standards, subtype);
+        media_type.set_charset(charset);
+        media_type
+    }
+
+    /// Creates the `text/plain; charset=utf-8` media type.
+    pub fn text_plain_utf8() -> MediaType {
+        MediaType::text("plain", Charset::Utf8)
+    }
+
+    /// Creates the `application/x-www-form-urlencoded` media type used for HTML form data.
+    pub fn form_urlencoded() -> MediaType {
+        MediaType::new(Application, Standards, "x-www-form-urlencoded")
+    }
+
+    /// Creates the `multipart/form-data` media type used for HTML forms containing files.
+    ///
+    /// A boundary parameter must be added before the media type can be used.
+    pub fn multipart_form_data() -> MediaType {
+        MediaType::new(Multipart, Standards, "form-data")
+    }
+
+    /// Checks if this is the wildcard media type `*/*` matching all types.
+    pub fn is_wildcard(&self) -> bool {
+        self.type_.is_none()
+    }
+
+    /// Checks if this media type has a concrete type but no subtype like `image/*`.
+    pub fn is_wildcard_subtype(&self) -> bool {
+        self.type_.is_some() && self.subtype.is_none()
+    }
+
     /// Accesses the tree component of the subtype.
     pub fn tree(&self) -> Option<&Tree> {
         if let Some(ref subtype) = self.subtype {
@@ -184,6 +389,28 @@ impl MediaType {
         }
     }
 
+    /// Returns the dot separated segments of the subtype without tree facet and suffix.
+    ///
+    /// `application/vnd.oasis.opendocument.text` yields `oasis`, `opendocument` and `text`,
+    /// `text/plain` only `plain`. Wildcards yield nothing.
+    pub fn subtype_facets(&self) -> impl Iterator<Item = &str> {
+        self.sub().into_iter().flat_map(|sub| sub.split('.'))
+    }
+
+    /// Returns the organization of a vendor or personal subtype.
+    ///
+    /// This is the first dot separated segment after the tree facet, `oasis` for
+    /// `application/vnd.oasis.opendocument.text`. Returns `None` for other trees or if the
+    /// subtype has no further dot.
+    pub fn vendor_prefix(&self) -> Option<&str> {
+        match self.subtype {
+            Some((Vendor, ref sub, _)) | Some((Personal, ref sub, _)) => {
+                sub.split_once('.').map(|(prefix, _)| prefix)
+            }
+            _ => None,
+        }
+    }
+
     /// Accesses the suffix of the type.
     pub fn suffix(&self) -> Option<&str> {
         if let Some(ref subtype) = self.subtype {
@@ -194,14 +421,27 @@ impl MediaType {
         None
     }
 
+    /// Returns the sub component together with the suffix like `svg+xml`.
+    ///
+    /// The registration tree is not included. For media types without a subtype `*` is
+    /// returned.
+    pub fn subtype_with_suffix(&self) -> Cow<'_, str> {
+        match self.subtype {
+            Some((_, ref sub, Some(ref suffix))) => Cow::Owned(format!("{}+{}", sub, suffix)),
+            Some((_, ref sub, None)) => Cow::Borrowed(&sub[..]),
+            None => Cow::Borrowed("*"),
+        }
+    }
+
     /// The boundary parameter is used to separate different blocks of multipart resources.
     ///
     /// It is defined in [RFC2046 - Multipurpose Internet Mail Extensions (MIME) Part Two:
     /// Media Types #5.1.  Multipart Media Type](https://tools.ietf.org/html/rfc2046#section-5.1).
+    /// The same rules apply to `multipart/byteranges` in HTTP range responses.
     pub fn boundary(&self) -> Result<&str> {
-        let boundary = try!(self.parameters.get("boundary").ok_or(Error::NotFound));
+        let boundary = self.parameters.get("boundary").ok_or(Error::NotFound)?;
         if !utils::boundary(boundary) {
-            return Err(Error::Invalid);
+            return Err(Error::InvalidParameter("boundary".to_owned()));
         }
         Ok(&boundary[..])
     }
@@ -213,9 +453,91 @@ impl MediaType {
     /// Media Types #4.1.2. Charset Parameter](https://tools.ietf.org/html/rfc2046#section-4.1.2)
     /// and [RFC6657 - Update to MIME regarding "charset" Parameter Handling in Textual Media Types]
     /// (https://tools.ietf.org/html/rfc6657).
+    ///
+    /// Returns `Error::NotFound` if there is no charset parameter and
+    /// `Error::InvalidParameter` if it is empty.
+    ///
+    /// Charset names are case-insensitive: the value is stored and displayed as it was given,
+    /// but it is parsed and compared by `semantic_eq()` ignoring case.
     pub fn charset(&self) -> Result<Charset> {
-        let charset = try!(self.parameters.get("charset").ok_or(Error::NotFound));
-        Ok(try!(charset.parse()))
+        let charset = self.parameters.get("charset").ok_or(Error::NotFound)?;
+        if charset.is_empty() {
+            return Err(Error::InvalidParameter("charset".to_owned()));
+        }
+        charset.parse().map_err(|_| Error::InvalidParameter("charset".to_owned()))
+    }
+
+    /// Distinguishes an absent charset parameter from a known and an unknown charset.
+    pub fn charset_state(&self) -> CharsetState {
+        match self.charset() {
+            Ok(Charset::Unregistered(name)) => CharsetState::Unknown(name),
+            Ok(charset) => CharsetState::Known(charset),
+            Err(Error::NotFound) => CharsetState::Absent,
+            Err(_) => {
+                let name = self.parameters.get("charset").map(|x| x.to_string());
+                CharsetState::Unknown(name.unwrap_or_default())
+            }
+        }
+    }
+
+    /// Returns the charset parameter, tolerating common misspellings.
+    ///
+    /// Unlike the strict `charset()` surrounding whitespace and quotes are removed and the
+    /// aliases `utf8`, `latin1`, `latin-1` and `ascii` are mapped to `utf-8`, `iso-8859-1`
+    /// and `us-ascii`. Returns `None` if there is no charset or it is still unknown.
+    pub fn charset_relaxed(&self) -> Option<Charset> {
+        let charset = self.parameters.get("charset")?;
+        let charset = charset.trim().trim_matches(|c| c == '"' || c == '\'').trim();
+        let charset = match &*utils::ascii_lowercase(charset) {
+            "utf8" => "utf-8",
+            "latin1" | "latin-1" => "iso-8859-1",
+            "ascii" => "us-ascii",
+            _ => charset,
+        };
+        match charset.parse() {
+            Ok(Charset::Unregistered(_)) | Err(_) => None,
+            Ok(charset) => Some(charset),
+        }
+    }
+
+    /// Decides the charset of a text resource given the charset of a detected byte order mark.
+    ///
+    /// A UTF-16 or UTF-32 byte order mark wins over the declared charset, otherwise the
+    /// declared charset is used, then any other byte order mark and finally the default of the
+    /// media type: `US-ASCII` for `text/*` as defined by RFC 2046 and `utf-8` for JSON.
+    /// The `charsets` crate does not know UTF-16 and UTF-32, they are recognized by their
+    /// unregistered names like `UTF-16LE`.
+    pub fn charset_respecting_bom(&self, bom: Option<Charset>) -> Option<Charset> {
+        if let Some(Charset::Unregistered(ref name)) = bom {
+            let name = name.to_ascii_lowercase();
+            if name.starts_with("utf-16") || name.starts_with("utf-32") {
+                return bom.clone();
+            }
+        }
+        if let Ok(charset) = self.charset() {
+            return Some(charset);
+        }
+        if bom.is_some() {
+            return bom;
+        }
+        if self.type_ == Some(Text) {
+            Some(Charset::UsAscii)
+        } else if MediaType::new(Application, Standards, "json").eq_mime_portion(self) ||
+                  self.suffix() == Some("json") {
+            Some(Charset::Utf8)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the charset parameter or calls `sniff` if there is none.
+    ///
+    /// An explicit charset always takes precedence, `sniff` can be used to detect the charset
+    /// from the content for example by looking at a byte order mark.
+    pub fn charset_or<F>(&self, sniff: F) -> Option<Charset>
+        where F: FnOnce() -> Option<Charset>
+    {
+        self.charset().ok().or_else(sniff)
     }
 
     /// Sets the charset parameter to the given charset and returns the old value if present.
@@ -223,21 +545,541 @@ impl MediaType {
         self.parameters.insert("charset".into(), Cow::Owned(charset.to_string()))
     }
 
+    /// Rewrites the charset parameter to the preferred name of the charset.
+    ///
+    /// The name is looked up with the `charsets` crate, for example `utf-8` stays `utf-8` and
+    /// `iso-8859-1` becomes `ISO-8859-1`. Unregistered charsets and aliases unknown to the
+    /// `charsets` crate like `latin1` are left unchanged.
+    pub fn normalize_charset(&mut self) {
+        match self.charset() {
+            Ok(Charset::Unregistered(_)) | Err(_) => (),
+            Ok(charset) => {
+                self.set_charset(charset);
+            }
+        }
+    }
+
     /// Sets the charset to UTF-8.
     pub fn set_charset_utf8(&mut self) -> Option<Cow<'static, str>> {
         self.set_charset(Charset::Utf8)
     }
 
+    /// Adds the parameters implied by the subtype if they are not present.
+    ///
+    /// `text/plain` gets `charset=US-ASCII` as defined by RFC 2046, `application/json` and
+    /// types with a `+json` suffix get `charset=utf-8`. Other parameters are never changed.
+    ///
+    /// A multipart boundary has no default, for `multipart/*` types without a boundary
+    /// `Error::NotFound` is returned.
+    pub fn apply_defaults(&mut self) -> Result<()> {
+        if self.type_ == Some(Multipart) && !self.parameters.contains_key("boundary") {
+            return Err(Error::NotFound);
+        }
+        if self.parameters.contains_key("charset") {
+            return Ok(());
+        }
+        if MediaType::new(Text, Standards, "plain").eq_mime_portion(self) {
+            self.set_charset(Charset::UsAscii);
+        } else if MediaType::new(Application, Standards, "json").eq_mime_portion(self) ||
+                  self.suffix() == Some("json") {
+            self.set_charset(Charset::Utf8);
+        }
+        Ok(())
+    }
+
+    /// Sets a parameter and returns the old value if present.
+    ///
+    /// The name must be a token and is stored lowercased. Values that are not tokens are
+    /// quoted when the media type is displayed, but they must not contain CR, LF or NUL.
+    /// Fails with `Error::Invalid` for a malformed name and with `Error::InvalidParameter` for a
+    /// malformed value, the media type is left unchanged.
+    pub fn set_parameter<K, V>(&mut self, name: K, value: V) -> Result<Option<Cow<'static, str>>>
+        where K: Into<Cow<'static, str>>,
+              V: Into<Cow<'static, str>>
+    {
+        let name = name.into();
+        let value = value.into();
+        if !utils::token(&name) {
+            return Err(Error::Invalid);
+        }
+        if !utils::representable_value(&value) {
+            return Err(Error::InvalidParameter(name.to_ascii_lowercase()));
+        }
+        let name = if name.bytes().any(|c| c.is_ascii_uppercase()) {
+            Cow::Owned(name.to_ascii_lowercase())
+        } else {
+            name
+        };
+        Ok(self.parameters.insert(name, unflagged(value)))
+    }
+
+    /// Replaces the top-level type, keeping the subtype and parameters.
+    pub fn set_type(&mut self, type_: Type) {
+        self.type_ = Some(type_);
+    }
+
+    /// Replaces the top-level type if the parameters stay meaningful.
+    ///
+    /// Fails with `Error::InvalidParameter` if the new type is not `multipart` but a boundary
+    /// parameter is present. The media type is left unchanged on error.
+    pub fn try_set_type(&mut self, type_: Type) -> Result<()> {
+        if type_ != Multipart && self.parameters.contains_key("boundary") {
+            return Err(Error::InvalidParameter("boundary".to_owned()));
+        }
+        self.set_type(type_);
+        Ok(())
+    }
+
+    /// Replaces the suffix of the subtype or removes it if `None` is given.
+    ///
+    /// Fails with `Error::NotFound` if there is no subtype and with `Error::InvalidSubtype` if
+    /// the suffix is not a token.
+    pub fn set_suffix<S>(&mut self, suffix: Option<S>) -> Result<()>
+        where S: Into<Cow<'static, str>>
+    {
+        let subtype = self.subtype.as_mut().ok_or(Error::NotFound)?;
+        let suffix = suffix.map(Into::into);
+        if let Some(ref suffix) = suffix {
+            if !utils::token(suffix) {
+                return Err(Error::InvalidSubtype);
+            }
+        }
+        subtype.2 = suffix;
+        Ok(())
+    }
+
+    /// Retains only the parameters for which the predicate returns true.
+    ///
+    /// The predicate is called with the lowercased parameter name and the value.
+    pub fn retain_parameters<F>(&mut self, mut f: F)
+        where F: FnMut(&str, &str) -> bool
+    {
+        self.parameters.retain(|key, value| f(&utils::ascii_lowercase(key), value));
+    }
+
+    /// Returns the number of parameters.
+    pub fn parameter_count(&self) -> usize {
+        self.parameters.len()
+    }
+
+    /// Checks if there is at least one parameter.
+    pub fn has_parameters(&self) -> bool {
+        !self.parameters.is_empty()
+    }
+
+    /// Returns an iterator over the names of all parameters.
+    pub fn parameter_names(&self) -> impl Iterator<Item = &str> {
+        self.parameters.keys().map(|key| &key[..])
+    }
+
+    /// Checks if a parameter is present, the name is compared case-insensitively.
+    pub fn has_param(&self, name: &str) -> bool {
+        self.parameter_names().any(|key| key.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the value of a parameter with `%XX` escapes decoded as UTF-8.
+    ///
+    /// This is a lenient convenience for the many non-conforming headers that percent-encode
+    /// plain parameter values, it is not the RFC 2231 extended parameter (`name*`) decoder.
+    /// Malformed escapes and escapes that do not decode to valid UTF-8 are kept literally,
+    /// the other escapes are still decoded.
+    pub fn get_param_decoded(&self, name: &str) -> Option<Cow<'_, str>> {
+        let value = self.parameters.get(name)?;
+        if !value.contains('%') {
+            return Some(Cow::Borrowed(&value[..]));
+        }
+        Some(Cow::Owned(utils::percent_decode(value)))
+    }
+
+    /// Returns the parameters in a canonical form suitable for hashing or serialization.
+    ///
+    /// Parameter names are lowercased and the pairs are sorted by name. The value of the
+    /// charset parameter is lowercased too as charsets are case-insensitive, all other values
+    /// are kept as they are.
+    pub fn canonical_parameters(&self) -> Vec<(Cow<'_, str>, Cow<'_, str>)> {
+        let mut parameters = Vec::with_capacity(self.parameters.len());
+        for (key, value) in &self.parameters {
+            let key = utils::ascii_lowercase(key);
+            let value = if key == "charset" {
+                utils::ascii_lowercase(value)
+            } else {
+                Cow::Borrowed(&value[..])
+            };
+            parameters.push((key, value));
+        }
+        parameters.sort();
+        parameters
+    }
+
+    /// Compares two media types ignoring differences that do not change their meaning.
+    ///
+    /// Type, subtype and parameter names are compared case-insensitively, as are the values
+    /// of the charset parameter. All other parameter values must be equal.
+    pub fn semantic_eq(&self, other: &MediaType) -> bool {
+        self.lowercase_essence() == other.lowercase_essence() &&
+        self.canonical_parameters() == other.canonical_parameters()
+    }
+
     /// Compares the mime type portion (the media type without parameters) of two media types.
     pub fn eq_mime_portion(&self, other: &MediaType) -> bool {
         self.type_ == other.type_ && self.subtype == other.subtype
     }
 
+    /// Compares type, tree, subtype, suffix and parameter names ignoring ASCII case, parameter
+    /// values are compared exactly.
+    ///
+    /// The parser lowercases type and subtype names, but media types constructed directly
+    /// keep their case and the derived `PartialEq` compares them byte by byte.
+    pub fn eq_ignore_ascii_case(&self, other: &MediaType) -> bool {
+        self.lowercase_essence() == other.lowercase_essence() &&
+        self.parameters.len() == other.parameters.len() &&
+        self.parameters.iter().all(|(key, value)| {
+            other.parameters
+                 .iter()
+                 .any(|(other_key, other_value)| {
+                     key.eq_ignore_ascii_case(other_key) && value == other_value
+                 })
+        })
+    }
+
+    /// Compares the mime type portion and all parameters except for the charset.
+    pub fn is_equivalent_ignoring_charset(&self, other: &MediaType) -> bool {
+        fn without_charset(media_type: &MediaType) -> Vec<(&str, &str)> {
+            let mut parameters: Vec<(&str, &str)> = media_type.parameters
+                                                              .iter()
+                                                              .map(|(k, v)| (&k[..], &v[..]))
+                                                              .filter(|&(k, _)| k != "charset")
+                                                              .collect();
+            parameters.sort();
+            parameters
+        }
+        self.eq_mime_portion(other) && without_charset(self) == without_charset(other)
+    }
+
     /// Returns true if the mime type portions differ, strict inverse of `eq_mime_portion()`.
     pub fn ne_mime_portion(&self, other: &MediaType) -> bool {
         !self.eq_mime_portion(other)
     }
 
+    /// Checks if this media type is matched by the given pattern.
+    ///
+    /// The pattern may be a wildcard like `*/*` or `image/*` or a suffix wildcard like
+    /// `application/*+json`, otherwise the type and subtype must be equal.
+    ///
+    /// Every media parameter of the pattern must be present in this media type with an equal
+    /// value. Parameter names and the value of `charset` are compared case-insensitively, other
+    /// values exactly. Parameters of this media type that are not in the pattern are ignored,
+    /// so `text/html; charset=utf-8` matches the pattern `text/html` but not the other way
+    /// round. The parameters `q` and `level` are only used in `Accept` headers and not
+    /// considered media parameters, they are ignored.
+    pub fn matches(&self, pattern: &MediaType) -> bool {
+        if pattern.type_.is_some() {
+            if pattern.type_ != self.type_ {
+                return false;
+            }
+            let subtype_matches = match pattern.subtype {
+                Some((Standards, ref sub, Some(ref suffix))) if sub == "*" => {
+                    self.matches_suffix_pattern(None, suffix)
+                }
+                Some(_) => pattern.subtype == self.subtype,
+                None => true,
+            };
+            if !subtype_matches {
+                return false;
+            }
+        }
+        pattern.parameters
+               .iter()
+               .filter(|&(key, _)| is_media_parameter(key))
+               .all(|(key, value)| {
+                   self.parameters.iter().any(|(own_key, own_value)| {
+                       own_key.eq_ignore_ascii_case(key) &&
+                       if key.eq_ignore_ascii_case("charset") {
+                           own_value.eq_ignore_ascii_case(value)
+                       } else {
+                           own_value == value
+                       }
+                   })
+               })
+    }
+
+    /// Checks if this media type has the given structured syntax suffix and top-level type.
+    ///
+    /// The type `None` matches all top-level types. The suffix is compared
+    /// case-insensitively, a media type without suffix like `application/json` does not
+    /// match the suffix `json`.
+    pub fn matches_suffix_pattern(&self, type_: Option<Type>, suffix: &str) -> bool {
+        if type_.is_some() && type_ != self.type_ {
+            return false;
+        }
+        match self.suffix() {
+            Some(own) => own.eq_ignore_ascii_case(suffix),
+            None => false,
+        }
+    }
+
+    /// Returns a copy where the values of the named parameters are replaced with
+    /// `[REDACTED]`, useful to log media types without leaking sensitive values.
+    ///
+    /// Parameter names are compared case-insensitively, the keys are kept.
+    pub fn redacted(&self, sensitive: &[&str]) -> MediaType {
+        let mut redacted = self.clone();
+        for (key, value) in &mut redacted.parameters {
+            if sensitive.iter().any(|name| key.eq_ignore_ascii_case(name)) {
+                *value = Cow::Borrowed("[REDACTED]");
+            }
+        }
+        redacted
+    }
+
+    /// Returns a deep copy where all strings are owned.
+    ///
+    /// The result does not borrow any data, it can be stored in a global cache or sent to
+    /// other threads.
+    pub fn to_owned_static(&self) -> MediaType {
+        fn owned(s: &str) -> Cow<'static, str> {
+            Cow::Owned(s.to_owned())
+        }
+        let type_ = self.type_.as_ref().map(|type_| match *type_ {
+            Type::Unregistered(ref name) => Type::Unregistered(owned(name)),
+            ref type_ => type_.clone(),
+        });
+        let subtype = self.subtype.as_ref().map(|(tree, sub, suffix)| {
+            let tree = match *tree {
+                Tree::Unregistered(ref facet) => Tree::Unregistered(owned(facet)),
+                ref tree => tree.clone(),
+            };
+            (tree, owned(sub), suffix.as_ref().map(|suffix| owned(suffix)))
+        });
+        MediaType {
+            type_,
+            subtype,
+            parameters: self.parameters
+                            .iter()
+                            .map(|(key, value)| {
+                                // Keeps the marker of parameters given without `=`.
+                                let value = match *value {
+                                    Cow::Borrowed("") => Cow::Borrowed(""),
+                                    ref value => owned(value),
+                                };
+                                (owned(key), value)
+                            })
+                            .collect(),
+        }
+    }
+
+    /// Returns the most specific media type that matches both media types.
+    ///
+    /// `text/html` and `text/plain` generalize to `text/*`, `text/html` and `image/png` to
+    /// `*/*`. Only parameters present with the same value in both are kept.
+    pub fn generalize(&self, other: &MediaType) -> MediaType {
+        let mut general = if self.type_ != other.type_ {
+            MediaType::wildcard()
+        } else if self.subtype != other.subtype {
+            MediaType {
+                type_: self.type_.clone(),
+                ..MediaType::default()
+            }
+        } else {
+            MediaType {
+                type_: self.type_.clone(),
+                subtype: self.subtype.clone(),
+                ..MediaType::default()
+            }
+        };
+        general.parameters = self.parameters
+                                 .iter()
+                                 .filter(|&(key, value)| other.parameters.get(key) == Some(value))
+                                 .map(|(key, value)| (key.clone(), value.clone()))
+                                 .collect();
+        general
+    }
+
+    /// Checks if this media type is matched by any of the patterns.
+    pub fn matches_any(&self, patterns: &[MediaType]) -> bool {
+        self.first_match(patterns).is_some()
+    }
+
+    /// Returns the first pattern matching this media type.
+    pub fn first_match<'a>(&self, patterns: &'a [MediaType]) -> Option<&'a MediaType> {
+        patterns.iter().find(|pattern| self.matches(pattern))
+    }
+
+    /// Checks if this offer is acceptable for the media ranges of an `Accept` header.
+    ///
+    /// The most specific matching range decides, so an explicit `q=0` excludes the offer even
+    /// if a broader range like `*/*` would accept it. Returns false if no range matches.
+    ///
+    /// Ranges are ranked by `specificity()` which only counts the parameters compared by
+    /// `matches()`, among equally specific ranges the highest quality wins.
+    pub fn is_acceptable(&self, accept: &[MediaType]) -> bool {
+        let mut best: Option<(u32, f32)> = None;
+        for range in accept.iter().filter(|range| self.matches(range)) {
+            let score = (range.specificity(), range.quality());
+            best = match best {
+                Some(best) if best >= score => Some(best),
+                _ => Some(score),
+            };
+        }
+        match best {
+            Some((_, quality)) => quality > 0.0,
+            None => false,
+        }
+    }
+
+    /// Formats the media type in the given letter case style.
+    ///
+    /// The `Display` implementation keeps the letter case of the stored values.
+    pub fn display_as(&self, style: DisplayStyle) -> impl Display + '_ {
+        StyledMediaType {
+            media_type: self,
+            style,
+        }
+    }
+
+    /// Serializes the media type in canonical form.
+    ///
+    /// Semantically equal media types always produce the same string: all names and the
+    /// charset value are lowercased, parameters are sorted and values are only quoted if they
+    /// are not tokens.
+    pub fn to_canonical_string(&self) -> String {
+        self.display_as(DisplayStyle::Canonical).to_string()
+    }
+
+    /// Serializes the media type like `Display` but fails with `Error::Invalid` if a parameter
+    /// name is not a token.
+    ///
+    /// Parameter names cannot be quoted, so `Display` would emit a malformed header.
+    pub fn try_to_string(&self) -> Result<String> {
+        if !self.parameter_names().all(utils::token) {
+            return Err(Error::Invalid);
+        }
+        Ok(self.to_string())
+    }
+
+    /// Serializes the media type like `Display` into bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_bytes(&mut bytes).expect("writing to a Vec cannot fail");
+        bytes
+    }
+
+    /// Writes the media type like `Display` to a byte sink without building a `String`.
+    pub fn write_bytes<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "{}", self)
+    }
+
+    /// Returns the length of the `Display` output without allocating it.
+    pub fn serialized_len(&self) -> usize {
+        let mut counter = utils::LengthCounter(0);
+        write!(counter, "{}", self).expect("counting cannot fail");
+        counter.0
+    }
+
+    /// Removes parameters until the serialized media type fits into `max_len` bytes.
+    ///
+    /// Parameters are removed in reverse alphabetical order of their names, parameters named
+    /// in `keep` (compared case-insensitively) are never removed, so the result may still
+    /// exceed the limit. Returns the removed parameters in the order they were removed.
+    pub fn truncate_to_len(&mut self,
+                           max_len: usize,
+                           keep: &[&str])
+                           -> Vec<(Cow<'static, str>, Cow<'static, str>)> {
+        let mut candidates: Vec<Cow<'static, str>> = self.parameters
+            .keys()
+            .filter(|key| !keep.iter().any(|keep| keep.eq_ignore_ascii_case(key)))
+            .cloned()
+            .collect();
+        candidates.sort();
+        let mut removed = Vec::new();
+        while self.serialized_len() > max_len {
+            let key = match candidates.pop() {
+                Some(key) => key,
+                None => break,
+            };
+            if let Some((key, value)) = self.parameters.remove_entry(&key) {
+                removed.push((key, value));
+            }
+        }
+        removed
+    }
+
+    /// Returns the lowercase media type without parameters in a single allocation.
+    ///
+    /// This is suitable as a key for interning media types.
+    pub fn essence_boxed(&self) -> Box<str> {
+        self.lowercase_essence().into_boxed_str()
+    }
+
+    /// Checks if the type and subtype names follow the restricted name grammar of
+    /// [RFC 6838 #4.2. Naming Requirements](https://tools.ietf.org/html/rfc6838#section-4.2).
+    ///
+    /// Names must start with a letter or digit and are at most 127 characters long, this is
+    /// stricter than the token rule used by the parser. Wildcards are not compliant.
+    pub fn is_spec_compliant_essence(&self) -> bool {
+        if self.subtype.is_none() {
+            return false;
+        }
+        let essence = self.lowercase_essence();
+        match essence.split_once('/') {
+            Some((type_, subtype)) => utils::restricted_name(type_) && utils::restricted_name(subtype),
+            None => false,
+        }
+    }
+
+    pub(crate) fn lowercase_essence(&self) -> String {
+        let mut counter = utils::LengthCounter(0);
+        self.write_essence(&mut counter).expect("counting cannot fail");
+        let mut essence = String::with_capacity(counter.0);
+        self.write_essence(&mut essence).expect("writing to a String cannot fail");
+        essence.make_ascii_lowercase();
+        essence
+    }
+
+    fn write_essence<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        if let Some(ref type_) = self.type_ {
+            write!(w, "{}/", type_)?;
+            if let Some((ref tree, ref subtype, ref suffix_opt)) = self.subtype {
+                write!(w, "{}{}", tree, subtype)?;
+                if let Some(ref suffix) = *suffix_opt {
+                    write!(w, "+{}", suffix)?;
+                }
+            } else {
+                w.write_str("*")?;
+            }
+        } else {
+            w.write_str("*/*")?;
+        }
+        Ok(())
+    }
+
+    /// Scores how specific this media range is to order ranges of equal quality.
+    ///
+    /// `*/*` scores 0, `type/*` scores 1 and `type/subtype` scores 2. Every media parameter
+    /// adds 1, these are the parameters compared by `matches()` so `q` and `level` are not
+    /// counted. This implements the precedence of media ranges from
+    /// [RFC7231 #5.3.2. Accept](https://tools.ietf.org/html/rfc7231#section-5.3.2).
+    pub fn specificity(&self) -> u32 {
+        let essence = if self.type_.is_none() {
+            0
+        } else if self.subtype.is_none() {
+            1
+        } else {
+            2
+        };
+        essence + self.parameter_names().filter(|name| is_media_parameter(name)).count() as u32
+    }
+
+    /// Returns the quality value of the `q` parameter as used in `Accept` headers.
+    ///
+    /// Defaults to 1 if the parameter is absent or malformed.
+    pub fn quality(&self) -> f32 {
+        match self.parameters.get("q") {
+            Some(q) => utils::parse_qvalue(q).unwrap_or(1.0),
+            None => 1.0,
+        }
+    }
+
     /// Checks if the media type is an image type.
     ///
     /// Implements the [MIME Sniffing standard]
@@ -260,15 +1102,7 @@ impl MediaType {
     /// Implements the [MIME Sniffing standard]
     /// (https://mimesniff.spec.whatwg.org/#mime-type-groups) for MIME type groups.
     pub fn is_font_type(&self) -> bool {
-        [MediaType::new(Application, Standards, "font-ttf"),
-         MediaType::new(Application, Standards, "font-cff"),
-         MediaType::new(Application, Standards, "font-off"),
-         MediaType::new(Application, Standards, "font-sfnt"),
-         MediaType::new(Application, Vendor, "ms-opentype"),
-         MediaType::new(Application, Standards, "font-woff"),
-         MediaType::new(Application, Vendor, "ms-fontobject")]
-            .iter()
-            .any(|x| x.eq_mime_portion(self))
+        self.is_essence_in(&FONT_TYPES)
     }
 
     /// Checks if the media type is a zip based type.
@@ -285,11 +1119,7 @@ impl MediaType {
     /// Implements the [MIME Sniffing standard]
     /// (https://mimesniff.spec.whatwg.org/#mime-type-groups) for MIME type groups.
     pub fn is_archive_type(&self) -> bool {
-        [MediaType::new(Application, Standards, "x-rar-compressed"),
-         MediaType::new(Application, Standards, "zip"),
-         MediaType::new(Application, Standards, "x-gzip")]
-            .iter()
-            .any(|x| x.eq_mime_portion(self))
+        self.is_essence_in(&ARCHIVE_TYPES)
     }
 
     /// Checks if the media type is an XML type.
@@ -297,10 +1127,37 @@ impl MediaType {
     /// Implements the [MIME Sniffing standard]
     /// (https://mimesniff.spec.whatwg.org/#mime-type-groups) for MIME type groups.
     pub fn is_xml_type(&self) -> bool {
-        self.suffix() == Some("xml") ||
-        [MediaType::new(Text, Standards, "xml"), MediaType::new(Application, Standards, "xml")]
-            .iter()
-            .any(|x| x.eq_mime_portion(self))
+        self.suffix() == Some("xml") || self.is_essence_in(&XML_TYPES)
+    }
+
+    /// Checks if the media type is a JavaScript MIME type.
+    ///
+    /// Implements the [MIME Sniffing standard]
+    /// (https://mimesniff.spec.whatwg.org/#javascript-mime-type-essence-match) JavaScript MIME
+    /// type essence match. Parameters are ignored but type and subtype must match exactly as
+    /// required for `X-Content-Type-Options: nosniff` checks.
+    pub fn is_javascript_mime_essence_match(&self) -> bool {
+        self.is_essence_in(&JAVASCRIPT_TYPES)
+    }
+
+    /// Checks if the media type is `text/css`, parameters are ignored.
+    pub fn is_css(&self) -> bool {
+        MediaType::new(Text, Standards, "css").eq_mime_portion(self)
+    }
+
+    /// Checks if the media type has a structured syntax suffix of a binary format.
+    ///
+    /// These are the suffixes `ber`, `der`, `cbor`, `cbor-seq`, `fastinfoset`, `wbxml`, `zip`
+    /// and `gzip` from the [Structured Syntax Suffix Registry]
+    /// (https://www.iana.org/assignments/media-type-structured-suffix).
+    pub fn is_binary_structured(&self) -> bool {
+        match self.suffix() {
+            Some(suffix) => {
+                ["ber", "der", "cbor", "cbor-seq", "fastinfoset", "wbxml", "zip", "gzip"]
+                    .contains(&suffix)
+            }
+            None => false,
+        }
     }
 
     /// Checks if the media type is a scriptable type, HTML or PDF.
@@ -308,17 +1165,204 @@ impl MediaType {
     /// Implements the [MIME Sniffing standard]
     /// (https://mimesniff.spec.whatwg.org/#mime-type-groups) for MIME type groups.
     pub fn is_scriptable_mime_type(&self) -> bool {
-        [MediaType::new(Text, Standards, "html"), MediaType::new(Application, Standards, "pdf")]
-            .iter()
-            .any(|x| x.eq_mime_portion(self))
+        self.is_essence_in(&SCRIPTABLE_TYPES)
+    }
+
+    /// Checks if the media type is one of the defaults sent by Apache for unknown files.
+    ///
+    /// These are `text/plain` without parameters or with a charset of exactly `ISO-8859-1`,
+    /// `iso-8859-1` or `UTF-8`. Implements the [MIME Sniffing standard]
+    /// (https://mimesniff.spec.whatwg.org/#interpreting-the-resource-metadata) check-for-apache-bug
+    /// flag.
+    pub fn is_apache_default_text(&self) -> bool {
+        if !MediaType::new(Text, Standards, "plain").eq_mime_portion(self) {
+            return false;
+        }
+        match self.parameters.len() {
+            0 => true,
+            1 => {
+                match self.parameters.get("charset") {
+                    Some(charset) => ["ISO-8859-1", "iso-8859-1", "UTF-8"].contains(&&charset[..]),
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Suggests a transfer encoding for entities of this media type.
+    ///
+    /// This is a heuristic: `text/*` types use quoted-printable, `multipart/*` and `message/*`
+    /// types use 7bit as their parts are encoded individually, all other types including
+    /// wildcards are assumed to be binary and use base64.
+    pub fn suggested_transfer_encoding(&self) -> TransferEncoding {
+        match self.type_ {
+            Some(Text) => TransferEncoding::QuotedPrintable,
+            Some(Multipart) | Some(Message) => TransferEncoding::SevenBit,
+            _ => TransferEncoding::Base64,
+        }
+    }
+
+    /// Checks if the media type is `application/x-www-form-urlencoded`.
+    pub fn is_form_urlencoded(&self) -> bool {
+        MediaType::form_urlencoded().eq_mime_portion(self)
+    }
+
+    /// Checks if the media type is `multipart/form-data`.
+    pub fn is_multipart_form_data(&self) -> bool {
+        MediaType::multipart_form_data().eq_mime_portion(self)
+    }
+
+    /// Checks if the media type is `multipart/byteranges` used for `206 Partial Content`
+    /// responses with multiple ranges.
+    ///
+    /// Its boundary follows the same rules as for other multipart types and is returned by
+    /// `boundary()`.
+    pub fn is_byteranges(&self) -> bool {
+        MediaType::new(Multipart, Standards, "byteranges").eq_mime_portion(self)
+    }
+
+    /// Checks if the media type is an MPEG-DASH manifest `application/dash+xml`.
+    pub fn is_dash_manifest(&self) -> bool {
+        MediaType::new_with_suffix(Application, Standards, "dash", "xml").eq_mime_portion(self)
+    }
+
+    /// Checks if the media type is an HTTP Live Streaming playlist.
+    ///
+    /// These are `application/vnd.apple.mpegurl` and the legacy `application/x-mpegurl`.
+    pub fn is_hls_manifest(&self) -> bool {
+        self.is_essence_in(&HLS_TYPES)
+    }
+
+    /// Checks if the media type is a Smooth Streaming manifest `application/vnd.ms-sstr+xml`.
+    pub fn is_smooth_streaming(&self) -> bool {
+        MediaType::new_with_suffix(Application, Vendor, "ms-sstr", "xml").eq_mime_portion(self)
+    }
+
+    /// Checks if the media type describes HTTP problem details as defined by RFC 7807.
+    ///
+    /// These are `application/problem+json` and `application/problem+xml`.
+    pub fn is_problem_details(&self) -> bool {
+        MediaType::new_with_suffix(Application, Standards, "problem", "json")
+            .eq_mime_portion(self) ||
+        MediaType::new_with_suffix(Application, Standards, "problem", "xml").eq_mime_portion(self)
+    }
+
+    /// Checks if type and subtype are equal to one of the essences and there is no suffix.
+    fn is_essence_in(&self, essences: &[(Type, Tree, &str)]) -> bool {
+        let (tree, sub) = match self.subtype {
+            Some((ref tree, ref sub, None)) => (tree, sub),
+            _ => return false,
+        };
+        essences.iter().any(|&(ref other_type, ref other_tree, other_sub)| {
+            self.type_.as_ref() == Some(other_type) && tree == other_tree && sub == other_sub
+        })
     }
 }
 
+static FONT_TYPES: [(Type, Tree, &str); 7] = [(Application, Standards, "font-ttf"),
+                                              (Application, Standards, "font-cff"),
+                                              (Application, Standards, "font-off"),
+                                              (Application, Standards, "font-sfnt"),
+                                              (Application, Vendor, "ms-opentype"),
+                                              (Application, Standards, "font-woff"),
+                                              (Application, Vendor, "ms-fontobject")];
+
+static ARCHIVE_TYPES: [(Type, Tree, &str); 3] = [(Application, Standards, "x-rar-compressed"),
+                                                 (Application, Standards, "zip"),
+                                                 (Application, Standards, "x-gzip")];
+
+static XML_TYPES: [(Type, Tree, &str); 2] = [(Text, Standards, "xml"),
+                                             (Application, Standards, "xml")];
+
+// The part before the first dot of `javascript1.x` is parsed as registration tree.
+static JAVASCRIPT_TYPES: [(Type, Tree, &str); 16] =
+    [(Application, Standards, "ecmascript"),
+     (Application, Standards, "javascript"),
+     (Application, Standards, "x-ecmascript"),
+     (Application, Standards, "x-javascript"),
+     (Text, Standards, "ecmascript"),
+     (Text, Standards, "javascript"),
+     (Text, Tree::Unregistered(Cow::Borrowed("javascript1")), "0"),
+     (Text, Tree::Unregistered(Cow::Borrowed("javascript1")), "1"),
+     (Text, Tree::Unregistered(Cow::Borrowed("javascript1")), "2"),
+     (Text, Tree::Unregistered(Cow::Borrowed("javascript1")), "3"),
+     (Text, Tree::Unregistered(Cow::Borrowed("javascript1")), "4"),
+     (Text, Tree::Unregistered(Cow::Borrowed("javascript1")), "5"),
+     (Text, Standards, "jscript"),
+     (Text, Standards, "livescript"),
+     (Text, Standards, "x-ecmascript"),
+     (Text, Standards, "x-javascript")];
+
+static SCRIPTABLE_TYPES: [(Type, Tree, &str); 2] = [(Text, Standards, "html"),
+                                                    (Application, Standards, "pdf")];
+
+static HLS_TYPES: [(Type, Tree, &str); 2] = [(Application, Vendor, "apple.mpegurl"),
+                                             (Application, Standards, "x-mpegurl")];
+
+/// Checks if a parameter is a media parameter and not one of the `Accept` header parameters
+/// `q` and `level`.
+fn is_media_parameter(name: &str) -> bool {
+    !name.eq_ignore_ascii_case("q") && !name.eq_ignore_ascii_case("level")
+}
+
 /// top-level type name / [ tree. ] subtype name [ +suffix ] [ ; parameters ]
 impl FromStr for MediaType {
     type Err = Error;
     fn from_str(s: &str) -> Result<MediaType> {
-        let (raw_type, raw_subtype, raw_parameters) = try!(utils::parse_media_type(s.as_bytes()));
+        MediaType::parse_with(s, &ParseOptions::default())
+    }
+}
+
+impl MediaType {
+    /// Parses only a list of parameters like `; charset=utf-8; format=flowed`.
+    ///
+    /// The leading semicolon may be omitted. Parameter names are lowercased.
+    pub fn parse_parameters_str(s: &str) -> Result<HashMap<Cow<'static, str>, Cow<'static, str>>> {
+        let raw_parameters = utils::parse_parameter_list(s.as_bytes(), &ParseOptions::default())?;
+        parameters_from_bytes(raw_parameters, false)
+    }
+
+    /// Parses a media type using the given options.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<MediaType> {
+        MediaType::parse_bytes_with(s.as_bytes(), options)
+    }
+
+    /// Parses a media type from anything byte-like such as header values.
+    ///
+    /// Avoids validating the whole input as UTF-8 first, only parameter values are decoded.
+    pub fn parse_bytes<B: AsRef<[u8]>>(b: B) -> Result<MediaType> {
+        MediaType::parse_bytes_with(b.as_ref(), &ParseOptions::default())
+    }
+
+    /// Parses a media type from raw header bytes using the given options.
+    ///
+    /// Parameter values must be valid UTF-8 unless `ParseOptions::lossy_values` is set.
+    pub fn parse_bytes_with(s: &[u8], options: &ParseOptions) -> Result<MediaType> {
+        let (raw_type, raw_subtype, (raw_parameters, raw_flags)) =
+            utils::parse_media_type(s, options)?;
+        let parameters = parameters_from_bytes(raw_parameters, options.lossy_values)?;
+        let mut media_type = MediaType::from_raw_parts(raw_type, raw_subtype, parameters)?;
+        for flag in raw_flags {
+            media_type.parameters.insert(String::from_utf8(flag)?.into(), Cow::Borrowed(""));
+        }
+        Ok(media_type)
+    }
+
+    /// Parses only the type and subtype, everything after the subtype is ignored.
+    ///
+    /// The type and subtype are validated like in `from_str()`, but parameters are neither
+    /// parsed nor validated and the returned media type has none. This is cheaper if only
+    /// the essence is needed, for example to log the content type.
+    pub fn parse_essence(s: &str) -> Result<MediaType> {
+        let (raw_type, raw_subtype) = utils::parse_essence(s.as_bytes())?;
+        MediaType::from_raw_parts(raw_type, raw_subtype, HashMap::new())
+    }
+
+    fn from_raw_parts(raw_type: Vec<u8>,
+                      raw_subtype: Vec<u8>,
+                      parameters: HashMap<Cow<'static, str>, Cow<'static, str>>)
+                      -> Result<MediaType> {
         let type_ = match &raw_type[..] {
             b"*" => None,
             b"text" => Some(Text),
@@ -329,79 +1373,249 @@ impl FromStr for MediaType {
             b"multipart" => Some(Multipart),
             b"message" => Some(Message),
             b"model" => Some(Model),
-            _ => Some(Type::Unregistered(Cow::Owned(try!(String::from_utf8(raw_type))))),
+            _ => Some(Type::Unregistered(Cow::Owned(String::from_utf8(raw_type)?))),
         };
-        let mut parameters = HashMap::new();
-        for (key, value) in raw_parameters {
-            parameters.insert(try!(String::from_utf8(key)).into(),
-                              try!(String::from_utf8(value)).into());
-        }
         if raw_subtype == b"*" {
             Ok(MediaType {
-                type_: type_,
+                type_,
                 subtype: None,
-                parameters: parameters,
-            })
+                parameters,
+                })
         } else {
-            let subtype = try!(String::from_utf8(raw_subtype));
-            let (prefix, suffix) = if subtype.contains('+') {
-                let mut parts = subtype.rsplitn(2, '+');
-                let suffix = parts.next().unwrap();
-                let prefix = parts.next().unwrap();
-                (prefix, Some(suffix))
-            } else {
-                (&subtype[..], None)
+            let subtype = String::from_utf8(raw_subtype)?;
+            let (prefix, suffix) = match subtype.rsplit_once('+') {
+                Some((prefix, suffix)) => (prefix, Some(suffix)),
+                None => (&subtype[..], None),
             };
-            let (tree, sub) = if prefix.contains('.') {
-                let mut parts = prefix.splitn(2, '.');
-                let tree = match parts.next().unwrap() {
-                    "vnd" => Vendor,
-                    "prs" => Personal,
-                    "x" => Private,
-                    s => Tree::Unregistered(Cow::Owned(s.to_owned())),
-                };
-                (tree, parts.next().unwrap())
-            } else {
-                (Standards, prefix)
+            if prefix.is_empty() || suffix == Some("") {
+                return Err(Error::InvalidSubtype);
+            }
+            let (tree, sub) = match prefix.split_once('.') {
+                Some((facet, sub)) => {
+                    if facet.is_empty() || sub.split('.').any(str::is_empty) {
+                        return Err(Error::InvalidSubtype);
+                    }
+                    let tree = match facet {
+                        "vnd" => Vendor,
+                        "prs" => Personal,
+                        "x" => Private,
+                        s => Tree::Unregistered(Cow::Owned(s.to_owned())),
+                    };
+                    (tree, sub)
+                }
+                None => (Standards, prefix),
             };
             Ok(MediaType {
-                type_: type_,
+                type_,
                 subtype: Some((tree,
                                Cow::Owned(sub.to_owned()),
                                suffix.map(|x| Cow::Owned(x.to_owned())))),
-                parameters: parameters,
-            })
+                parameters,
+                })
+        }
+    }
+
+    /// Splits a media type into its tokens with byte spans, useful to debug how a header
+    /// is parsed.
+    ///
+    /// See `lexer::tokenize()` for details.
+    pub fn tokenize(s: &str) -> impl Iterator<Item = Result<lexer::Token>> {
+        lexer::tokenize(s).into_iter()
+    }
+
+    /// Parses a comma separated list of media types.
+    ///
+    /// Commas inside quoted strings do not separate entries and empty entries are skipped.
+    /// Unlike `MediaTypeList` the parse fails if any entry is not a valid media type.
+    pub fn parse_all(s: &str) -> Result<Vec<MediaType>> {
+        utils::split_list(s).into_iter().map(str::parse).collect()
+    }
+
+    /// Parses the first media type of a comma separated list and ignores the rest.
+    ///
+    /// Commas inside quoted strings do not separate entries and empty entries are skipped.
+    /// Fails with `Error::MissingSeparator` like an empty media type if there is no entry.
+    pub fn parse_first(s: &str) -> Result<MediaType> {
+        match utils::split_list(s).first() {
+            Some(first) => first.parse(),
+            None => Err(Error::MissingSeparator),
         }
     }
+
+    /// Parses a media type or returns the given default if parsing fails.
+    ///
+    /// Useful to fall back to a safe type like `application/octet-stream` for empty or
+    /// malformed headers.
+    pub fn from_str_or_default(s: &str, default: MediaType) -> MediaType {
+        s.parse().unwrap_or(default)
+    }
 }
 
-impl Display for MediaType {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        if let Some(ref type_) = self.type_ {
-            try!(write!(f, "{}/", type_));
-            if let Some((ref tree, ref subtype, ref suffix_opt)) = self.subtype {
-                try!(tree.fmt(f));
-                try!(subtype.fmt(f));
-                if let Some(ref suffix) = *suffix_opt {
-                    try!(write!(f, "+{}", suffix));
-                }
-            } else {
-                try!(f.write_str("*"));
-            }
+fn parameters_from_bytes(raw_parameters: HashMap<Vec<u8>, Vec<u8>>,
+                         lossy: bool)
+                         -> Result<HashMap<Cow<'static, str>, Cow<'static, str>>> {
+    let mut parameters = HashMap::new();
+    for (key, value) in raw_parameters {
+        let value = if lossy {
+            String::from_utf8_lossy(&value).into_owned()
         } else {
-            try!(f.write_str("*/*"))
+            String::from_utf8(value)?
+        };
+        parameters.insert(String::from_utf8(key)?.into(), value.into());
+    }
+    Ok(parameters)
+}
+
+/// Converts a top-level type into a media type matching all of its subtypes.
+///
+/// ```
+/// use media_types::{MediaType, Text};
+///
+/// assert_eq!(MediaType::from(Text).to_string(), "text/*");
+/// ```
+impl From<Type> for MediaType {
+    fn from(type_: Type) -> MediaType {
+        MediaType::wildcard_subtype(type_)
+    }
+}
+
+/// Creates a media type from a type, tree and subtype, the subtype must be a token.
+impl<'a> TryFrom<(Type, Tree, &'a str)> for MediaType {
+    type Error = Error;
+    fn try_from((type_, tree, subtype): (Type, Tree, &'a str)) -> Result<MediaType> {
+        if !utils::token(subtype) {
+            return Err(Error::InvalidSubtype);
+        }
+        Ok(MediaType::new(type_, tree, subtype.to_owned()))
+    }
+}
+
+/// Creates a media type from a type, tree, subtype and suffix, the subtype and suffix must be
+/// tokens.
+impl<'a, 'b> TryFrom<(Type, Tree, &'a str, &'b str)> for MediaType {
+    type Error = Error;
+    fn try_from((type_, tree, subtype, suffix): (Type, Tree, &'a str, &'b str))
+                -> Result<MediaType> {
+        if !utils::token(subtype) || !utils::token(suffix) {
+            return Err(Error::InvalidSubtype);
         }
+        Ok(MediaType::new_with_suffix(type_, tree, subtype.to_owned(), suffix.to_owned()))
+    }
+}
+
+/// Adds parameters to the media type, parameter names are lowercased.
+impl Extend<(Cow<'static, str>, Cow<'static, str>)> for MediaType {
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item = (Cow<'static, str>, Cow<'static, str>)>
+    {
+        for (key, value) in iter {
+            let key = match utils::ascii_lowercase(&key) {
+                Cow::Owned(lowercase) => Cow::Owned(lowercase),
+                Cow::Borrowed(_) => key,
+            };
+            self.parameters.insert(key, unflagged(value));
+        }
+    }
+}
+
+impl fmt::Debug for MediaType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if f.alternate() {
+            return f.debug_struct("MediaType")
+                    .field("type_", &self.type_)
+                    .field("subtype", &self.subtype)
+                    .field("parameters", &self.parameters)
+                    .finish();
+        }
+        write!(f, "MediaType {{ {} }}", self)
+    }
+}
+
+impl Display for MediaType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.write_essence(f)?;
         let mut items: Vec<(&Cow<'static, str>, &Cow<'static, str>)> = self.parameters
                                                                            .iter()
                                                                            .collect();
-        items.sort_by(|&(ref first, _), &(ref second, _)| first.cmp(second));
-        for (ref key, ref value) in items {
-            if utils::token(&value) {
-                try!(write!(f, "; {}={}", key, value));
-            } else {
-                try!(write!(f, "; {}=\"{}\"", key, value));
+        items.sort_by_key(|&(key, _)| key);
+        for (key, value) in items {
+            write_parameter(f, key, value, matches!(*value, Cow::Borrowed("")))?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the marker of a parameter given without `=` with an empty value that is written
+/// as `name=""`.
+fn unflagged(value: Cow<'static, str>) -> Cow<'static, str> {
+    match value {
+        Cow::Borrowed("") => Cow::Owned(String::new()),
+        value => value,
+    }
+}
+
+fn write_parameter(f: &mut Formatter, key: &str, value: &str, flag: bool) -> fmt::Result {
+    if flag {
+        return write!(f, "; {}", key);
+    }
+    if !utils::needs_quoting(key, value) {
+        return write!(f, "; {}={}", key, value);
+    }
+    write!(f, "; {}=\"", key)?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+/// Controls the letter case of media types formatted with `MediaType::display_as()`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplayStyle {
+    /// Lowercases the type, the subtype with its tree and suffix, and the parameter names.
+    Lowercase,
+    /// Like `Lowercase` but also lowercases the value of the charset parameter as charset names
+    /// are case-insensitive. Parameters given without `=` are written as `name=""`.
+    Canonical,
+}
+
+struct StyledMediaType<'a> {
+    media_type: &'a MediaType,
+    style: DisplayStyle,
+}
+
+impl<'a> Display for StyledMediaType<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.media_type.lowercase_essence())?;
+        let parameters = match self.style {
+            DisplayStyle::Lowercase => {
+                let mut parameters: Vec<(Cow<str>, Cow<str>, bool)> =
+                    self.media_type
+                        .parameters
+                        .iter()
+                        .map(|(key, value)| {
+                            let flag = matches!(*value, Cow::Borrowed(""));
+                            (utils::ascii_lowercase(key), Cow::Borrowed(&value[..]), flag)
+                        })
+                        .collect();
+                parameters.sort();
+                parameters
             }
+            // Flags are written as `name=""` as they are equal to empty values.
+            DisplayStyle::Canonical => {
+                self.media_type
+                    .canonical_parameters()
+                    .into_iter()
+                    .map(|(key, value)| (key, value, false))
+                    .collect()
+            }
+        };
+        for (key, value, flag) in parameters {
+            write_parameter(f, &key, &value, flag)?;
         }
         Ok(())
     }
 }
+
@@ -0,0 +1,125 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::FromIterator;
+use std::ops::Deref;
+use std::slice;
+use std::str::FromStr;
+use std::vec;
+
+use error::{Error, Result};
+use utils;
+use MediaType;
+
+/// A comma separated list of media types like the value of an `Accept` header.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MediaTypeList(Vec<MediaType>);
+
+impl MediaTypeList {
+    /// Creates an empty list.
+    pub fn new() -> MediaTypeList {
+        MediaTypeList(Vec::new())
+    }
+
+    /// Appends a media type to the end of the list.
+    pub fn push(&mut self, media_type: MediaType) {
+        self.0.push(media_type);
+    }
+
+    /// Removes all media types that are equal to an earlier entry, keeping the first occurrence.
+    pub fn dedup(&mut self) {
+        let mut unique: Vec<MediaType> = Vec::with_capacity(self.0.len());
+        for media_type in self.0.drain(..) {
+            if !unique.contains(&media_type) {
+                unique.push(media_type);
+            }
+        }
+        self.0 = unique;
+    }
+
+    /// Removes media types with the same type and subtype as another entry, keeping the one
+    /// with the highest quality value.
+    ///
+    /// The kept entry takes the position of the first occurrence, on equal quality the earlier
+    /// entry is kept.
+    pub fn dedup_by_essence(&mut self) {
+        let mut unique: Vec<(String, MediaType)> = Vec::with_capacity(self.0.len());
+        for media_type in self.0.drain(..) {
+            let essence = media_type.lowercase_essence();
+            match unique.iter().position(|(other, _)| *other == essence) {
+                Some(i) => {
+                    if media_type.quality() > unique[i].1.quality() {
+                        unique[i].1 = media_type;
+                    }
+                }
+                None => unique.push((essence, media_type)),
+            }
+        }
+        self.0 = unique.into_iter().map(|(_, media_type)| media_type).collect();
+    }
+
+    /// Returns the media types as a vector.
+    pub fn into_vec(self) -> Vec<MediaType> {
+        self.0
+    }
+}
+
+impl Deref for MediaTypeList {
+    type Target = [MediaType];
+
+    fn deref(&self) -> &[MediaType] {
+        &self.0
+    }
+}
+
+impl From<Vec<MediaType>> for MediaTypeList {
+    fn from(media_types: Vec<MediaType>) -> MediaTypeList {
+        MediaTypeList(media_types)
+    }
+}
+
+impl FromIterator<MediaType> for MediaTypeList {
+    fn from_iter<I: IntoIterator<Item = MediaType>>(iter: I) -> MediaTypeList {
+        MediaTypeList(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for MediaTypeList {
+    type Item = MediaType;
+    type IntoIter = vec::IntoIter<MediaType>;
+
+    fn into_iter(self) -> vec::IntoIter<MediaType> {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a MediaTypeList {
+    type Item = &'a MediaType;
+    type IntoIter = slice::Iter<'a, MediaType>;
+
+    fn into_iter(self) -> slice::Iter<'a, MediaType> {
+        self.0.iter()
+    }
+}
+
+/// Parses the list leniently: elements that are not valid media types are skipped, as user
+/// agents commonly send slightly malformed `Accept` headers.
+impl FromStr for MediaTypeList {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<MediaTypeList> {
+        Ok(MediaTypeList(utils::split_list(s)
+                             .into_iter()
+                             .filter_map(|x| x.parse().ok())
+                             .collect()))
+    }
+}
+
+impl Display for MediaTypeList {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (i, media_type) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            media_type.fmt(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+/// Creates a `MediaType` from a string literal that is checked at compile time.
+///
+/// A malformed literal is a compile error. Besides the checks of the parser the literal
+/// must follow the strict grammar: type and subtype are tokens, there is no whitespace
+/// around `=` and quoted strings are closed.
+///
+/// Only the validation happens at compile time. The literal is still parsed at runtime
+/// each time the macro is evaluated, this parse cannot fail because every literal accepted
+/// by the check is accepted by the parser.
+///
+/// ```
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("text/html; charset=utf-8");
+/// assert_eq!(media_type.to_string(), "text/html; charset=utf-8");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("text/html; charset");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("application/+json");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("text");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("text/.x");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("text/plain; a=\"b");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("text/plain; a = b");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate media_types;
+///
+/// # fn main() {
+/// let media_type = media_type!("text/plain; aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+///     aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa=b");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! media_type {
+    ($s:literal) => {{
+        const _: () = assert!($crate::__is_valid_media_type_literal($s),
+                              "invalid media type literal");
+        <$crate::MediaType as ::std::str::FromStr>::from_str($s)
+            .expect("media type literal was checked at compile time")
+    }};
+}
+
+/// Checks a media type literal for the `media_type!` macro.
+///
+/// Everything accepted here is accepted by the parser.
+pub const fn is_valid_literal(s: &str) -> bool {
+    let b = s.as_bytes();
+    let mut i = skip_whitespace(b, 0);
+    let start = i;
+    i = skip_token(b, i);
+    if i == start || i - start > 127 || i >= b.len() || b[i] != b'/' {
+        return false;
+    }
+    i += 1;
+    let start = i;
+    i = skip_token(b, i);
+    if i == start || i - start > 127 || !valid_tree(b, start, i) {
+        return false;
+    }
+    let mut count = 0;
+    loop {
+        i = skip_whitespace(b, i);
+        if i == b.len() {
+            return true;
+        }
+        if b[i] != b';' {
+            return false;
+        }
+        i = skip_whitespace(b, i + 1);
+        let start = i;
+        i = skip_token(b, i);
+        count += 1;
+        if i == start || i - start > 127 || count > 128 || i >= b.len() || b[i] != b'=' {
+            return false;
+        }
+        i += 1;
+        if i < b.len() && b[i] == b'"' {
+            i += 1;
+            loop {
+                if i >= b.len() {
+                    return false;
+                }
+                if b[i] == b'"' {
+                    break;
+                }
+                if b[i] == b'\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+        } else {
+            let start = i;
+            i = skip_token(b, i);
+            if i == start {
+                return false;
+            }
+        }
+    }
+}
+
+/// Rejects an empty subtype or suffix around `+` and empty dot separated segments before the
+/// suffix, like the parser does.
+const fn valid_tree(b: &[u8], start: usize, end: usize) -> bool {
+    let mut prefix_end = end;
+    let mut i = start;
+    while i < end {
+        if b[i] == b'+' {
+            prefix_end = i;
+        }
+        i += 1;
+    }
+    if prefix_end == start || (prefix_end < end && prefix_end + 1 == end) {
+        return false;
+    }
+    let mut has_dot = false;
+    let mut i = start;
+    while i < prefix_end {
+        if b[i] == b'.' {
+            has_dot = true;
+        }
+        i += 1;
+    }
+    if !has_dot {
+        return true;
+    }
+    let mut i = start;
+    let mut segment_start = start;
+    while i <= prefix_end {
+        if i == prefix_end || b[i] == b'.' {
+            if i == segment_start {
+                return false;
+            }
+            segment_start = i + 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn skip_token(b: &[u8], mut i: usize) -> usize {
+    while i < b.len() && is_tchar(b[i]) {
+        i += 1;
+    }
+    i
+}
+
+const fn skip_whitespace(b: &[u8], mut i: usize) -> usize {
+    while i < b.len() && (b[i] == b' ' || b[i] == b'\t') {
+        i += 1;
+    }
+    i
+}
+
+const fn is_tchar(c: u8) -> bool {
+    matches!(c,
+             b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^' |
+             b'_' | b'`' | b'|' | b'~' | b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z')
+}
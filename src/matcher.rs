@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+
+use MediaType;
+
+/// A fixed set of media types that can be checked for an equal essence in constant time.
+///
+/// Type and subtype are compared case-insensitively, parameters are ignored. This replaces a
+/// linear scan with `eq_mime_portion()` for large sets of known types.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MimeMatcher {
+    essences: HashSet<Box<str>>,
+}
+
+impl MimeMatcher {
+    /// Builds a matcher from the essences of the given media types.
+    pub fn new(media_types: &[MediaType]) -> MimeMatcher {
+        MimeMatcher { essences: media_types.iter().map(MediaType::essence_boxed).collect() }
+    }
+
+    /// Checks if the candidate has the same type and subtype as one of the media types.
+    pub fn contains_essence(&self, candidate: &MediaType) -> bool {
+        self.essences.contains(&*candidate.lowercase_essence())
+    }
+
+    /// Returns the number of distinct essences.
+    pub fn len(&self) -> usize {
+        self.essences.len()
+    }
+
+    /// Checks if the matcher contains no essences.
+    pub fn is_empty(&self) -> bool {
+        self.essences.is_empty()
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use error::{Error, Result};
+use MediaType;
+
+/// A parsed media type that keeps the original text.
+///
+/// The text can be echoed exactly as it was received while comparisons use the normalized
+/// media type. Type, subtype and parameter names are lowercased by the parser and the value
+/// of the charset parameter is lowercased as charset names are case-insensitive.
+#[derive(Clone, Debug)]
+pub struct NormalizedMediaType {
+    original: String,
+    normalized: MediaType,
+}
+
+impl NormalizedMediaType {
+    /// Returns the text the media type was parsed from.
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// Returns the normalized media type.
+    pub fn normalized(&self) -> &MediaType {
+        &self.normalized
+    }
+}
+
+impl FromStr for NormalizedMediaType {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<NormalizedMediaType> {
+        let mut normalized: MediaType = s.parse()?;
+        if let Some(charset) = normalized.parameters.get_mut("charset") {
+            if charset.bytes().any(|c| c.is_ascii_uppercase()) {
+                *charset = charset.to_ascii_lowercase().into();
+            }
+        }
+        Ok(NormalizedMediaType {
+            original: s.to_owned(),
+            normalized,
+        })
+    }
+}
+
+/// Compares the normalized media types, the original text is ignored.
+impl PartialEq for NormalizedMediaType {
+    fn eq(&self, other: &NormalizedMediaType) -> bool {
+        self.normalized == other.normalized
+    }
+}
+
+impl Eq for NormalizedMediaType {}
+
+/// Writes the original text.
+impl Display for NormalizedMediaType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
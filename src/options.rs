@@ -0,0 +1,43 @@
+/// Options to customize parsing of media types.
+///
+/// The default options are used by `FromStr`, they only accept media types
+/// following the specification.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseOptions {
+    /// The maximum number of parameters. If the media type contains more parameters
+    /// parsing fails with `Error::TooManyParameters`. Bounds the work done for untrusted input.
+    pub max_parameters: usize,
+    /// Removes comments in parentheses as used in email headers, for example
+    /// `text/plain (plain text); charset=utf-8`. HTTP does not allow comments in media types.
+    pub strip_comments: bool,
+    /// Unfolds header values spanning multiple lines, each line break followed by whitespace
+    /// is replaced with a single space.
+    pub unfold_lines: bool,
+    /// Accepts a top-level type without a subtype like `image` and treats it as `image/*`.
+    /// The specification requires the slash and subtype.
+    pub allow_missing_subtype: bool,
+    /// Replaces invalid UTF-8 in parameter values with U+FFFD instead of failing with
+    /// `Error::Utf8Error`, so the type and subtype stay usable.
+    pub lossy_values: bool,
+    /// Ignores a leading `Content-Type:` field name as found in copied header lines like
+    /// `Content-Type: text/html`. The field name is matched case-insensitively.
+    pub strip_field_name: bool,
+    /// Accepts parameters separated by whitespace instead of semicolons like
+    /// `text/plain charset=utf-8`, as sent by some broken producers. Only used if the
+    /// parameters contain no semicolon at all.
+    pub allow_whitespace_param_separator: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            max_parameters: 128,
+            strip_comments: false,
+            unfold_lines: false,
+            allow_missing_subtype: false,
+            lossy_values: false,
+            strip_field_name: false,
+            allow_whitespace_param_separator: false,
+        }
+    }
+}
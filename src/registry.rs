@@ -0,0 +1,2101 @@
+//! A curated table of common media types.
+//!
+//! This is not the full IANA registry but covers the types commonly found on the web and in
+//! email, it can be used offline for validation and autocompletion. The table holds about 400
+//! types taken from the IANA registry and the widely used Apache `mime.types` file, rare
+//! vendor types are not included.
+
+use utils;
+use MediaType;
+
+/// Information about a well-known media type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RegistryEntry {
+    /// The lowercase media type without parameters like `image/png`.
+    pub essence: &'static str,
+    /// File extensions used for the media type, the preferred extension comes first.
+    pub extensions: &'static [&'static str],
+    /// True for textual formats, false for binary formats.
+    pub is_text: bool,
+}
+
+impl RegistryEntry {
+    /// Returns the preferred file extension.
+    pub fn extension(&self) -> &'static str {
+        self.extensions[0]
+    }
+}
+
+/// Looks up a media type like `image/png` in the table, the name is compared
+/// case-insensitively.
+pub fn lookup(type_subtype: &str) -> Option<RegistryEntry> {
+    let key = utils::ascii_lowercase(type_subtype.trim());
+    ENTRIES.binary_search_by(|entry| entry.essence.cmp(&key))
+           .ok()
+           .map(|i| ENTRIES[i])
+}
+
+/// Returns all entries of the table sorted by essence.
+pub fn entries() -> &'static [RegistryEntry] {
+    ENTRIES
+}
+
+/// The [MIME type group](https://mimesniff.spec.whatwg.org/#mime-type-groups) of a media type
+/// as returned by `MediaType::mime_group()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MimeGroup {
+    /// An image type.
+    Image,
+    /// An audio or video type.
+    AudioOrVideo,
+    /// A font type.
+    Font,
+    /// An archive type like `application/zip`.
+    Archive,
+    /// Another zip based type like `application/epub+zip`.
+    ZipBased,
+    /// A JavaScript type.
+    JavaScript,
+    /// An XML type.
+    Xml,
+    /// A scriptable type, HTML or PDF.
+    Scriptable,
+    /// None of the groups above.
+    Other,
+}
+
+/// File naming and classification hints for a media type as returned by
+/// `MediaType::ext_hint()`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExtHint {
+    /// The preferred file extension if the media type is in the table.
+    pub extension: Option<&'static str>,
+    /// Other file extensions used for the media type.
+    pub alternates: &'static [&'static str],
+    /// The MIME type group.
+    pub group: MimeGroup,
+}
+
+impl MediaType {
+    /// Looks up the media type in the curated registry, parameters are ignored.
+    pub fn registry_entry(&self) -> Option<RegistryEntry> {
+        lookup(&self.lowercase_essence())
+    }
+
+    /// Checks if the media type is in the curated registry, parameters are ignored.
+    ///
+    /// Types missing from the table are not necessarily invalid, but a false result can be
+    /// used to warn about made-up types in user input.
+    pub fn is_well_known(&self) -> bool {
+        self.registry_entry().is_some()
+    }
+
+    /// Classifies the media type into a MIME type group.
+    ///
+    /// Archives are reported as `Archive` even though they are zip based, the first matching
+    /// group in declaration order wins.
+    pub fn mime_group(&self) -> MimeGroup {
+        if self.is_image_type() {
+            MimeGroup::Image
+        } else if self.is_audio_or_video_type() {
+            MimeGroup::AudioOrVideo
+        } else if self.is_font_type() {
+            MimeGroup::Font
+        } else if self.is_archive_type() {
+            MimeGroup::Archive
+        } else if self.is_zip_based_type() {
+            MimeGroup::ZipBased
+        } else if self.is_javascript_mime_essence_match() {
+            MimeGroup::JavaScript
+        } else if self.is_xml_type() {
+            MimeGroup::Xml
+        } else if self.is_scriptable_mime_type() {
+            MimeGroup::Scriptable
+        } else {
+            MimeGroup::Other
+        }
+    }
+
+    /// Returns the preferred and alternate file extensions together with the MIME type group.
+    pub fn ext_hint(&self) -> ExtHint {
+        let entry = self.registry_entry();
+        ExtHint {
+            extension: entry.map(|entry| entry.extension()),
+            alternates: entry.map_or(&[], |entry| &entry.extensions[1..]),
+            group: self.mime_group(),
+        }
+    }
+}
+
+static ENTRIES: &[RegistryEntry] = &[
+    RegistryEntry {
+        essence: "application/andrew-inset",
+        extensions: &["ez"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/applixware",
+        extensions: &["aw"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/atom+xml",
+        extensions: &["atom"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/cbor",
+        extensions: &["cbor"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/dicom",
+        extensions: &["dcm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/ecmascript",
+        extensions: &["es", "ecma"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/epub+zip",
+        extensions: &["epub"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/geo+json",
+        extensions: &["geojson"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/graphql",
+        extensions: &["graphql"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/gzip",
+        extensions: &["gz"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/hta",
+        extensions: &["hta"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/java-archive",
+        extensions: &["jar"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/javascript",
+        extensions: &["js", "mjs"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/jose",
+        extensions: &["jose"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/jrd+json",
+        extensions: &["jrd"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/json",
+        extensions: &["json"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/json5",
+        extensions: &["json5"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/jwk+json",
+        extensions: &["jwk"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/ld+json",
+        extensions: &["jsonld"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/mac-binhex40",
+        extensions: &["hqx"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/manifest+json",
+        extensions: &["webmanifest"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/marc",
+        extensions: &["mrc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/mathml+xml",
+        extensions: &["mathml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/mbox",
+        extensions: &["mbox"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/mp4",
+        extensions: &["mp4s", "m4p"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/msgpack",
+        extensions: &["msgpack"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/msword",
+        extensions: &["doc", "dot"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/mxf",
+        extensions: &["mxf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/n-quads",
+        extensions: &["nq"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/n-triples",
+        extensions: &["nt"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/octet-stream",
+        extensions: &["bin"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/oda",
+        extensions: &["oda"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/oebps-package+xml",
+        extensions: &["opf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/ogg",
+        extensions: &["ogx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/onenote",
+        extensions: &["onetoc", "onetoc2", "onetmp", "onepkg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pdf",
+        extensions: &["pdf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pgp-encrypted",
+        extensions: &["pgp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pgp-keys",
+        extensions: &["asc"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/pgp-signature",
+        extensions: &["asc", "sig"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/pics-rules",
+        extensions: &["prf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/pkcs10",
+        extensions: &["p10"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pkcs7-mime",
+        extensions: &["p7m", "p7c"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pkcs7-signature",
+        extensions: &["p7s"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pkcs8",
+        extensions: &["p8"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pkix-cert",
+        extensions: &["cer"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pkix-crl",
+        extensions: &["crl"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pkix-pkipath",
+        extensions: &["pkipath"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/pls+xml",
+        extensions: &["pls"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/postscript",
+        extensions: &["ps", "ai", "eps"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/protobuf",
+        extensions: &["proto"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/raml+yaml",
+        extensions: &["raml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/rdf+xml",
+        extensions: &["rdf", "owl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/rss+xml",
+        extensions: &["rss"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/rtf",
+        extensions: &["rtf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/schema+json",
+        extensions: &["json"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/sdp",
+        extensions: &["sdp"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/smil+xml",
+        extensions: &["smil", "smi"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/sparql-query",
+        extensions: &["rq"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/sparql-results+xml",
+        extensions: &["srx"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/sql",
+        extensions: &["sql"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/srgs",
+        extensions: &["gram"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/srgs+xml",
+        extensions: &["grxml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/ssml+xml",
+        extensions: &["ssml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/toml",
+        extensions: &["toml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/vnd.amazon.ebook",
+        extensions: &["azw"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.android.package-archive",
+        extensions: &["apk"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.api+json",
+        extensions: &["json"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/vnd.apple.installer+xml",
+        extensions: &["mpkg"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/vnd.apple.keynote",
+        extensions: &["key"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.apple.mpegurl",
+        extensions: &["m3u8"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/vnd.apple.numbers",
+        extensions: &["numbers"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.apple.pages",
+        extensions: &["pages"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.apple.pkpass",
+        extensions: &["pkpass"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.debian.binary-package",
+        extensions: &["deb", "udeb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.geogebra.file",
+        extensions: &["ggb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.google-earth.kml+xml",
+        extensions: &["kml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/vnd.google-earth.kmz",
+        extensions: &["kmz"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.mozilla.xul+xml",
+        extensions: &["xul"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-cab-compressed",
+        extensions: &["cab"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-excel",
+        extensions: &["xls"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-excel.addin.macroenabled.12",
+        extensions: &["xlam"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-excel.sheet.binary.macroenabled.12",
+        extensions: &["xlsb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-excel.sheet.macroenabled.12",
+        extensions: &["xlsm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-excel.template.macroenabled.12",
+        extensions: &["xltm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-fontobject",
+        extensions: &["eot"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-htmlhelp",
+        extensions: &["chm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-outlook",
+        extensions: &["msg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-pki.seccat",
+        extensions: &["cat"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-pki.stl",
+        extensions: &["stl"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-powerpoint",
+        extensions: &["ppt"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-powerpoint.addin.macroenabled.12",
+        extensions: &["ppam"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-powerpoint.presentation.macroenabled.12",
+        extensions: &["pptm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-powerpoint.slideshow.macroenabled.12",
+        extensions: &["ppsm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-powerpoint.template.macroenabled.12",
+        extensions: &["potm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-project",
+        extensions: &["mpp", "mpt"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-word.document.macroenabled.12",
+        extensions: &["docm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-word.template.macroenabled.12",
+        extensions: &["dotm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-works",
+        extensions: &["wps", "wks", "wcm", "wdb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.ms-xpsdocument",
+        extensions: &["xps"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.chart",
+        extensions: &["odc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.database",
+        extensions: &["odb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.formula",
+        extensions: &["odf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.graphics",
+        extensions: &["odg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.graphics-template",
+        extensions: &["otg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.image",
+        extensions: &["odi"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.presentation",
+        extensions: &["odp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.presentation-template",
+        extensions: &["otp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.spreadsheet",
+        extensions: &["ods"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.spreadsheet-template",
+        extensions: &["ots"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.text",
+        extensions: &["odt"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.text-master",
+        extensions: &["odm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.text-template",
+        extensions: &["ott"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.oasis.opendocument.text-web",
+        extensions: &["oth"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        extensions: &["pptx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.openxmlformats-officedocument.presentationml.slideshow",
+        extensions: &["ppsx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.openxmlformats-officedocument.presentationml.template",
+        extensions: &["potx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        extensions: &["xlsx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.openxmlformats-officedocument.spreadsheetml.template",
+        extensions: &["xltx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        extensions: &["docx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.openxmlformats-officedocument.wordprocessingml.template",
+        extensions: &["dotx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.rar",
+        extensions: &["rar"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.rn-realmedia",
+        extensions: &["rm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.sqlite3",
+        extensions: &["sqlite", "db"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.sun.xml.calc",
+        extensions: &["sxc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.sun.xml.draw",
+        extensions: &["sxd"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.sun.xml.impress",
+        extensions: &["sxi"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.sun.xml.writer",
+        extensions: &["sxw"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.tcpdump.pcap",
+        extensions: &["pcap", "cap", "dmp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.visio",
+        extensions: &["vsd", "vst", "vss", "vsw"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.wap.wbxml",
+        extensions: &["wbxml"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.wap.wmlc",
+        extensions: &["wmlc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.wap.wmlscriptc",
+        extensions: &["wmlsc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/vnd.xara",
+        extensions: &["xar"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/voicexml+xml",
+        extensions: &["vxml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/wasm",
+        extensions: &["wasm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/wsdl+xml",
+        extensions: &["wsdl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-7z-compressed",
+        extensions: &["7z"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-abiword",
+        extensions: &["abw"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-apple-diskimage",
+        extensions: &["dmg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-bittorrent",
+        extensions: &["torrent"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-bzip",
+        extensions: &["bz"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-bzip2",
+        extensions: &["bz2"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-cdlink",
+        extensions: &["vcd"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-chrome-extension",
+        extensions: &["crx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-cpio",
+        extensions: &["cpio"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-csh",
+        extensions: &["csh"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-debian-package",
+        extensions: &["deb", "udeb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-dvi",
+        extensions: &["dvi"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-font-bdf",
+        extensions: &["bdf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-font-pcf",
+        extensions: &["pcf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-freearc",
+        extensions: &["arc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-gtar",
+        extensions: &["gtar"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-hdf",
+        extensions: &["hdf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-httpd-php",
+        extensions: &["php"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-iso9660-image",
+        extensions: &["iso"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-java-jnlp-file",
+        extensions: &["jnlp"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-latex",
+        extensions: &["latex"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-lzh-compressed",
+        extensions: &["lzh", "lha"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-mobipocket-ebook",
+        extensions: &["mobi", "prc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-ms-application",
+        extensions: &["application"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-ms-shortcut",
+        extensions: &["lnk"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-msdownload",
+        extensions: &["exe", "dll", "com", "bat", "msi"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-msmetafile",
+        extensions: &["wmf", "wmz", "emf", "emz"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-netcdf",
+        extensions: &["nc", "cdf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-ns-proxy-autoconfig",
+        extensions: &["pac"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-perl",
+        extensions: &["pl", "pm"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-pkcs12",
+        extensions: &["p12", "pfx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-pkcs7-certificates",
+        extensions: &["p7b", "spc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-pkcs7-certreqresp",
+        extensions: &["p7r"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-python-code",
+        extensions: &["pyc", "pyo"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-rar-compressed",
+        extensions: &["rar"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-redhat-package-manager",
+        extensions: &["rpm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-sea",
+        extensions: &["sea"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-sh",
+        extensions: &["sh"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-shar",
+        extensions: &["shar"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-shockwave-flash",
+        extensions: &["swf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-silverlight-app",
+        extensions: &["xap"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-stuffit",
+        extensions: &["sit"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-sv4cpio",
+        extensions: &["sv4cpio"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-sv4crc",
+        extensions: &["sv4crc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-tar",
+        extensions: &["tar"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-tcl",
+        extensions: &["tcl", "tk"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-tex",
+        extensions: &["tex"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-texinfo",
+        extensions: &["texinfo", "texi"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-troff-man",
+        extensions: &["man"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-troff-me",
+        extensions: &["me"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-troff-ms",
+        extensions: &["ms"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-ustar",
+        extensions: &["ustar"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-wais-source",
+        extensions: &["src"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/x-x509-ca-cert",
+        extensions: &["der", "crt", "pem"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-xpinstall",
+        extensions: &["xpi"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/x-xz",
+        extensions: &["xz"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/xhtml+xml",
+        extensions: &["xhtml", "xht"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/xml",
+        extensions: &["xml", "xsl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/xml-dtd",
+        extensions: &["dtd"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/xop+xml",
+        extensions: &["xop"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/xslt+xml",
+        extensions: &["xslt", "xsl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/xspf+xml",
+        extensions: &["xspf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/yaml",
+        extensions: &["yaml", "yml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/yang",
+        extensions: &["yang"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "application/zip",
+        extensions: &["zip"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "application/zstd",
+        extensions: &["zst"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/3gpp",
+        extensions: &["3gpp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/3gpp2",
+        extensions: &["3g2"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/aac",
+        extensions: &["aac"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/adpcm",
+        extensions: &["adp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/aiff",
+        extensions: &["aiff", "aif", "aifc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/amr",
+        extensions: &["amr"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/basic",
+        extensions: &["au", "snd"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/flac",
+        extensions: &["flac"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/midi",
+        extensions: &["mid", "midi"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/mp3",
+        extensions: &["mp3"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/mp4",
+        extensions: &["m4a"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/mpeg",
+        extensions: &["mp3", "mpga"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/ogg",
+        extensions: &["oga", "ogg", "opus"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/opus",
+        extensions: &["opus"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/s3m",
+        extensions: &["s3m"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/silk",
+        extensions: &["sil"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/vnd.dts",
+        extensions: &["dts"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/vnd.dts.hd",
+        extensions: &["dtshd"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/vnd.rn-realaudio",
+        extensions: &["ra"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/vnd.wave",
+        extensions: &["wav"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/wav",
+        extensions: &["wav"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/wave",
+        extensions: &["wav"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/webm",
+        extensions: &["weba"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-aac",
+        extensions: &["aac"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-aiff",
+        extensions: &["aif", "aiff", "aifc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-caf",
+        extensions: &["caf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-flac",
+        extensions: &["flac"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-m4a",
+        extensions: &["m4a"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-matroska",
+        extensions: &["mka"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-mpegurl",
+        extensions: &["m3u"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "audio/x-ms-wax",
+        extensions: &["wax"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-ms-wma",
+        extensions: &["wma"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-pn-realaudio",
+        extensions: &["ram", "ra"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/x-wav",
+        extensions: &["wav"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "audio/xm",
+        extensions: &["xm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "font/collection",
+        extensions: &["ttc"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "font/otf",
+        extensions: &["otf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "font/sfnt",
+        extensions: &["sfnt"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "font/ttf",
+        extensions: &["ttf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "font/woff",
+        extensions: &["woff"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "font/woff2",
+        extensions: &["woff2"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/apng",
+        extensions: &["apng"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/avif",
+        extensions: &["avif"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/bmp",
+        extensions: &["bmp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/cgm",
+        extensions: &["cgm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/g3fax",
+        extensions: &["g3"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/gif",
+        extensions: &["gif"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/heic",
+        extensions: &["heic"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/heic-sequence",
+        extensions: &["heics"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/heif",
+        extensions: &["heif"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/heif-sequence",
+        extensions: &["heifs"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/ief",
+        extensions: &["ief"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/jls",
+        extensions: &["jls"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/jp2",
+        extensions: &["jp2", "jpg2"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/jpeg",
+        extensions: &["jpg", "jpeg", "jpe"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/jpm",
+        extensions: &["jpm", "jpgm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/jpx",
+        extensions: &["jpx", "jpf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/jxl",
+        extensions: &["jxl"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/jxr",
+        extensions: &["jxr"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/ktx",
+        extensions: &["ktx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/ktx2",
+        extensions: &["ktx2"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/pjpeg",
+        extensions: &["jfif"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/png",
+        extensions: &["png"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/svg+xml",
+        extensions: &["svg", "svgz"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "image/tiff",
+        extensions: &["tif", "tiff"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.adobe.photoshop",
+        extensions: &["psd"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.djvu",
+        extensions: &["djvu", "djv"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.dwg",
+        extensions: &["dwg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.dxf",
+        extensions: &["dxf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.fastbidsheet",
+        extensions: &["fbs"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.microsoft.icon",
+        extensions: &["ico"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.ms-dds",
+        extensions: &["dds"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/vnd.wap.wbmp",
+        extensions: &["wbmp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/webp",
+        extensions: &["webp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-cmu-raster",
+        extensions: &["ras"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-icon",
+        extensions: &["ico"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-ms-bmp",
+        extensions: &["bmp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-pcx",
+        extensions: &["pcx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-pict",
+        extensions: &["pic", "pct"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-portable-anymap",
+        extensions: &["pnm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-portable-bitmap",
+        extensions: &["pbm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-portable-graymap",
+        extensions: &["pgm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-portable-pixmap",
+        extensions: &["ppm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-rgb",
+        extensions: &["rgb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-tga",
+        extensions: &["tga"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "image/x-xbitmap",
+        extensions: &["xbm"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "image/x-xpixmap",
+        extensions: &["xpm"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "image/x-xwindowdump",
+        extensions: &["xwd"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "message/global",
+        extensions: &["u8msg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "message/rfc822",
+        extensions: &["eml", "mime"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "model/3mf",
+        extensions: &["3mf"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/gltf+json",
+        extensions: &["gltf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "model/gltf-binary",
+        extensions: &["glb"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/iges",
+        extensions: &["igs", "iges"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/mesh",
+        extensions: &["msh", "mesh", "silo"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/mtl",
+        extensions: &["mtl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "model/obj",
+        extensions: &["obj"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "model/step",
+        extensions: &["stp", "step"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/stl",
+        extensions: &["stl"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/usd",
+        extensions: &["usd"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/vnd.collada+xml",
+        extensions: &["dae"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "model/vnd.usdz+zip",
+        extensions: &["usdz"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "model/vrml",
+        extensions: &["wrl", "vrml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "model/x3d+xml",
+        extensions: &["x3d", "x3dz"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/cache-manifest",
+        extensions: &["appcache", "manifest"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/calendar",
+        extensions: &["ics"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/coffeescript",
+        extensions: &["coffee", "litcoffee"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/css",
+        extensions: &["css"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/csv",
+        extensions: &["csv"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/dns",
+        extensions: &["soa", "zone"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/html",
+        extensions: &["html", "htm"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/jade",
+        extensions: &["jade"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/javascript",
+        extensions: &["js", "mjs"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/jsx",
+        extensions: &["jsx"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/less",
+        extensions: &["less"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/markdown",
+        extensions: &["md", "markdown"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/mathml",
+        extensions: &["mml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/mdx",
+        extensions: &["mdx"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/n3",
+        extensions: &["n3"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/plain",
+        extensions: &["txt", "text", "log"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/richtext",
+        extensions: &["rtx"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/rtf",
+        extensions: &["rtf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/sgml",
+        extensions: &["sgml", "sgm"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/shex",
+        extensions: &["shex"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/slim",
+        extensions: &["slim", "slm"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/spdx",
+        extensions: &["spdx"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/stylus",
+        extensions: &["stylus", "styl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/tab-separated-values",
+        extensions: &["tsv"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/troff",
+        extensions: &["t", "tr", "roff", "man", "me", "ms"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/turtle",
+        extensions: &["ttl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/uri-list",
+        extensions: &["uri", "uris", "urls"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/vcard",
+        extensions: &["vcf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/vnd.curl",
+        extensions: &["curl"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/vnd.graphviz",
+        extensions: &["gv"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/vnd.wap.wml",
+        extensions: &["wml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/vnd.wap.wmlscript",
+        extensions: &["wmls"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/vtt",
+        extensions: &["vtt"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-asm",
+        extensions: &["s", "asm"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-c",
+        extensions: &["c", "cc", "cxx", "cpp", "h", "hh", "dic"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-component",
+        extensions: &["htc"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-fortran",
+        extensions: &["f", "for", "f77", "f90"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-handlebars-template",
+        extensions: &["hbs"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-java-source",
+        extensions: &["java"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-lua",
+        extensions: &["lua"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-markdown",
+        extensions: &["mkd"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-nfo",
+        extensions: &["nfo"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-opml",
+        extensions: &["opml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-org",
+        extensions: &["org"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-pascal",
+        extensions: &["p", "pas"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-processing",
+        extensions: &["pde"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-sass",
+        extensions: &["sass"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-scss",
+        extensions: &["scss"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-setext",
+        extensions: &["etx"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-sfv",
+        extensions: &["sfv"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-suse-ymp",
+        extensions: &["ymp"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-uuencode",
+        extensions: &["uu"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-vcalendar",
+        extensions: &["vcs"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/x-vcard",
+        extensions: &["vcf"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/xml",
+        extensions: &["xml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/xml-external-parsed-entity",
+        extensions: &["ent"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "text/yaml",
+        extensions: &["yaml", "yml"],
+        is_text: true,
+    },
+    RegistryEntry {
+        essence: "video/3gpp",
+        extensions: &["3gp", "3gpp"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/3gpp2",
+        extensions: &["3g2"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/h261",
+        extensions: &["h261"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/h263",
+        extensions: &["h263"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/h264",
+        extensions: &["h264"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/iso.segment",
+        extensions: &["m4s"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/jpeg",
+        extensions: &["jpgv"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/jpm",
+        extensions: &["jpm", "jpgm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/mj2",
+        extensions: &["mj2", "mjp2"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/mp2t",
+        extensions: &["ts"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/mp4",
+        extensions: &["mp4", "m4v"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/mpeg",
+        extensions: &["mpeg", "mpg"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/ogg",
+        extensions: &["ogv"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/quicktime",
+        extensions: &["mov", "qt"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/vnd.dlna.mpeg-tts",
+        extensions: &["ts"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/vnd.mpegurl",
+        extensions: &["mxu", "m4u"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/vnd.vivo",
+        extensions: &["viv"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/webm",
+        extensions: &["webm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-f4v",
+        extensions: &["f4v"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-fli",
+        extensions: &["fli"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-flv",
+        extensions: &["flv"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-m4v",
+        extensions: &["m4v"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-matroska",
+        extensions: &["mkv"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-mng",
+        extensions: &["mng"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-ms-asf",
+        extensions: &["asf", "asx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-ms-wm",
+        extensions: &["wm"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-ms-wmv",
+        extensions: &["wmv"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-ms-wmx",
+        extensions: &["wmx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-ms-wvx",
+        extensions: &["wvx"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-msvideo",
+        extensions: &["avi"],
+        is_text: false,
+    },
+    RegistryEntry {
+        essence: "video/x-sgi-movie",
+        extensions: &["movie"],
+        is_text: false,
+    },
+];
@@ -0,0 +1,63 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use error::{Error, Result};
+use MediaType;
+
+/// A media type behind a reference count that is cheap to clone and share between threads.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SharedMediaType(Arc<MediaType>);
+
+impl SharedMediaType {
+    /// Wraps a media type so it can be shared.
+    pub fn new(media_type: MediaType) -> SharedMediaType {
+        SharedMediaType(Arc::new(media_type))
+    }
+
+    /// Checks if both values share the same media type allocation.
+    pub fn ptr_eq(&self, other: &SharedMediaType) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for SharedMediaType {
+    type Target = MediaType;
+
+    fn deref(&self) -> &MediaType {
+        &self.0
+    }
+}
+
+impl From<MediaType> for SharedMediaType {
+    fn from(media_type: MediaType) -> SharedMediaType {
+        SharedMediaType::new(media_type)
+    }
+}
+
+/// Unwraps the media type, cloning it only if it is still shared.
+impl From<SharedMediaType> for MediaType {
+    fn from(shared: SharedMediaType) -> MediaType {
+        Arc::try_unwrap(shared.0).unwrap_or_else(|arc| (*arc).clone())
+    }
+}
+
+impl PartialEq<MediaType> for SharedMediaType {
+    fn eq(&self, other: &MediaType) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl FromStr for SharedMediaType {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<SharedMediaType> {
+        s.parse().map(SharedMediaType::new)
+    }
+}
+
+impl Display for SharedMediaType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
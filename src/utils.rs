@@ -1,16 +1,20 @@
-use std::ascii::AsciiExt;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
+use std::str;
 
 use error::{Error, Result};
+use lexer::Lexer;
+use options::ParseOptions;
 
 /// `ALPHA =  %x41-5A / %x61-7A ; A-Z / a-z`
 pub fn alpha(c: char) -> bool {
-    c >= 'A' && c <= 'Z' || c >= 'a' && c <= 'z'
+    c.is_ascii_alphabetic()
 }
 
 /// `DIGIT = %x30-39 ; 0-9`
 pub fn digit(c: char) -> bool {
-    c >= '0' && c <= '9'
+    c.is_ascii_digit()
 }
 
 /// `tchar = "!" / "#" / "$" / "%" / "&" / "'" / "*"
@@ -37,16 +41,195 @@ pub fn bchars(c: char) -> bool {
 
 /// `token = 1*tchar`
 pub fn token(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(tchar)
+    token_bytes(s.as_bytes())
+}
+
+/// Like `token` but works on bytes without decoding UTF-8 first.
+pub fn token_bytes(s: &[u8]) -> bool {
+    !s.is_empty() && s.iter().all(|&c| tchar(c as char))
+}
+
+/// `restricted-name-chars = ALPHA / DIGIT / "!" / "#" / "$" / "&" / "-" / "^" / "_"
+/// / "." / "+"`
+pub fn restricted_name_char(c: char) -> bool {
+    digit(c) || alpha(c) || c == '!' || c == '#' || c == '$' || c == '&' || c == '-' ||
+    c == '^' || c == '_' || c == '.' || c == '+'
+}
+
+/// `restricted-name = restricted-name-first *126restricted-name-chars` where
+/// `restricted-name-first = ALPHA / DIGIT`
+pub fn restricted_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if digit(c) || alpha(c) => s.len() <= 127 && chars.all(restricted_name_char),
+        _ => false,
+    }
+}
+
+/// `attr-char = ALPHA / DIGIT / "!" / "#" / "$" / "&" / "+" / "-" / "." / "^" / "_" / "`"
+/// / "|" / "~"`
+pub fn attr_char(c: char) -> bool {
+    digit(c) || alpha(c) || c == '!' || c == '#' || c == '$' || c == '&' || c == '+' ||
+    c == '-' || c == '.' || c == '^' || c == '_' || c == '`' || c == '|' || c == '~'
+}
+
+/// `mime-charsetc = ALPHA / DIGIT / "!" / "#" / "$" / "%" / "&" / "+" / "-" / "^" / "_" / "`"
+/// / "{" / "}" / "~"`
+pub fn mime_charsetc(c: char) -> bool {
+    digit(c) || alpha(c) || c == '!' || c == '#' || c == '$' || c == '%' || c == '&' ||
+    c == '+' || c == '-' || c == '^' || c == '_' || c == '`' || c == '{' || c == '}' || c == '~'
+}
+
+/// `ext-value = charset  "'" [ language ] "'" value-chars` from RFC 5987 where
+/// `value-chars = *( pct-encoded / attr-char )`
+pub fn ext_value(s: &str) -> bool {
+    let mut parts = s.splitn(3, '\'');
+    let (charset, language, value) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(charset), Some(language), Some(value)) => (charset, language, value),
+        _ => return false,
+    };
+    if !charset.chars().all(mime_charsetc) ||
+       !language.chars().all(|c| alpha(c) || digit(c) || c == '-') {
+        return false;
+    }
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            match (bytes.get(i + 1).cloned().and_then(hex_value),
+                   bytes.get(i + 2).cloned().and_then(hex_value)) {
+                (Some(_), Some(_)) => i += 3,
+                _ => return false,
+            }
+        } else if attr_char(bytes[i] as char) {
+            i += 1;
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+/// Checks if the value of the named parameter must be written as quoted string.
+///
+/// Extended parameters from RFC 2231 like `title*` must not be quoted.
+pub fn needs_quoting(name: &str, value: &str) -> bool {
+    !(token_bytes(value.as_bytes()) || (name.ends_with('*') && ext_value(value)))
+}
+
+/// Checks that a parameter value can be written, even quoted it must not contain CR, LF or NUL.
+pub fn representable_value(s: &str) -> bool {
+    !s.bytes().any(|c| c == b'\r' || c == b'\n' || c == 0)
 }
 
 /// boundary := 0*69<bchars> bcharsnospace
 pub fn boundary(s: &str) -> bool {
-    !s.is_empty() && s.len() <= 70 && s.chars().all(bchars) &&
-    bcharsnospace(s.chars().last().unwrap())
+    boundary_bytes(s.as_bytes())
 }
 
-fn is_whitespace(c: u8) -> bool {
+/// Like `boundary` but works on bytes without decoding UTF-8 first.
+pub fn boundary_bytes(s: &[u8]) -> bool {
+    match s.last() {
+        Some(&last) => {
+            s.len() <= 70 && s.iter().all(|&c| bchars(c as char)) && bcharsnospace(last as char)
+        }
+        None => false,
+    }
+}
+
+/// Lowercases ASCII letters, only allocating if there are uppercase letters.
+pub fn ascii_lowercase(s: &str) -> Cow<'_, str> {
+    if s.bytes().any(|c| c.is_ascii_uppercase()) {
+        Cow::Owned(s.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Counts the bytes written to it, used to allocate strings of the exact size.
+pub struct LengthCounter(pub usize);
+
+impl fmt::Write for LengthCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        _ => None,
+    }
+}
+
+/// Decodes `%XX` escapes, copying malformed escapes literally.
+///
+/// Escapes that do not decode to valid UTF-8 are kept literally too, the other escapes of
+/// the string are still decoded.
+pub fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let mut escaped = Vec::new();
+        while bytes[i..].len() >= 3 && bytes[i] == b'%' {
+            match (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                (Some(high), Some(low)) => escaped.push(high << 4 | low),
+                _ => break,
+            }
+            i += 3;
+        }
+        if escaped.is_empty() {
+            let len = s[i..].chars().next().map_or(1, char::len_utf8);
+            decoded.push_str(&s[i..i + len]);
+            i += len;
+            continue;
+        }
+        // Each decoded byte of the run was written as three characters in `s`.
+        let mut run = &escaped[..];
+        let mut source = &s[start..i];
+        loop {
+            match str::from_utf8(run) {
+                Ok(valid) => {
+                    decoded.push_str(valid);
+                    break;
+                }
+                Err(err) => {
+                    let valid = err.valid_up_to();
+                    let invalid = err.error_len().unwrap_or(run.len() - valid);
+                    decoded.push_str(str::from_utf8(&run[..valid]).unwrap_or_default());
+                    decoded.push_str(&source[3 * valid..3 * (valid + invalid)]);
+                    run = &run[valid + invalid..];
+                    source = &source[3 * (valid + invalid)..];
+                }
+            }
+        }
+    }
+    decoded
+}
+
+/// `qvalue = ( "0" [ "." 0*3DIGIT ] ) / ( "1" [ "." 0*3("0") ] )`
+pub fn parse_qvalue(s: &str) -> Result<f32> {
+    let (integer, fraction) = match s.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (s, ""),
+    };
+    let valid_fraction = match integer {
+        "0" => fraction.bytes().all(|c| c.is_ascii_digit()),
+        "1" => fraction.bytes().all(|c| c == b'0'),
+        _ => false,
+    };
+    if !valid_fraction || fraction.len() > 3 {
+        return Err(Error::Invalid);
+    }
+    s.parse().map_err(|_| Error::Invalid)
+}
+
+pub fn is_whitespace(c: u8) -> bool {
     c == b' ' || c == b'\n' || c == b'\r' || c == b'\t'
 }
 
@@ -55,18 +238,33 @@ fn is_undefined(sequence: &[u8], s: usize) -> bool {
 }
 
 pub type Bytes = Vec<u8>;
+/// Parameters as parsed, the names of bare flags are listed separately.
+pub type RawParameters = (HashMap<Bytes, Bytes>, Vec<Bytes>);
 
-pub fn parse_type_portion(sequence: &[u8], s: &mut usize) -> Result<(Bytes, Bytes)> {
+pub fn parse_type_portion(sequence: &[u8],
+                          s: &mut usize,
+                          options: &ParseOptions)
+                          -> Result<(Bytes, Bytes)> {
     let mut type_ = Vec::new();
     let mut subtype = Vec::new();
     let mut t: u8 = 0;
     loop {
-        if t > 127 || is_undefined(sequence, *s) {
-            return Err(Error::Invalid);
+        if t > 127 {
+            return Err(Error::NameTooLong);
+        }
+        if options.allow_missing_subtype && !type_.is_empty() &&
+           (is_undefined(sequence, *s) || is_whitespace(sequence[*s]) || sequence[*s] == b';') {
+            return Ok((type_, b"*".to_vec()));
+        }
+        if is_undefined(sequence, *s) {
+            return Err(Error::MissingSeparator);
         }
         if sequence[*s] == b'/' {
             break;
         }
+        if !sequence[*s].is_ascii() {
+            return Err(Error::InvalidType);
+        }
         type_.push(sequence[*s].to_ascii_lowercase());
         *s += 1;
         t += 1;
@@ -75,18 +273,24 @@ pub fn parse_type_portion(sequence: &[u8], s: &mut usize) -> Result<(Bytes, Byte
     let mut u: u8 = 0;
     loop {
         if u > 127 {
-            return Err(Error::Invalid);
+            return Err(Error::NameTooLong);
         }
         if is_undefined(sequence, *s) {
-            return Ok((type_, subtype));
+            break;
         }
         if is_whitespace(sequence[*s]) || sequence[*s] == b';' {
             break;
         }
+        if !sequence[*s].is_ascii() {
+            return Err(Error::InvalidSubtype);
+        }
         subtype.push(sequence[*s].to_ascii_lowercase());
         *s += 1;
         u += 1;
     }
+    if subtype.is_empty() {
+        return Err(Error::InvalidSubtype);
+    }
     Ok((type_, subtype))
 }
 
@@ -96,34 +300,35 @@ fn parse_value(sequence: &[u8], s: &mut usize) -> Bytes {
         return value;
     }
     if sequence[*s] == b'"' {
-        *s += 1;
-        'M3: loop {
-            if is_undefined(sequence, *s) || sequence[*s] == b'"' {
-                if sequence[*s] == b'"' {
-                    *s += 1
-                }
-                return value;
-            }
-            if sequence[*s] == b'\\' && !is_undefined(sequence, *s + 1) {
-                *s += 1;
-            }
-            value.push(sequence[*s]);
-            *s += 1;
-        }
-    } else {
-        'M4: loop {
-            if is_undefined(sequence, *s) || is_whitespace(sequence[*s]) || sequence[*s] == b';' {
-                return value;
-            }
-            value.push(sequence[*s]);
-            *s += 1;
+        let mut lexer = Lexer::at(sequence, *s);
+        value = lexer.quoted_string().unwrap_or_default();
+        *s = lexer.position();
+        return value;
+    }
+    loop {
+        if is_undefined(sequence, *s) || is_whitespace(sequence[*s]) || sequence[*s] == b';' {
+            return value;
         }
+        value.push(sequence[*s]);
+        *s += 1;
     }
 }
 
-fn parse_parameters(sequence: &[u8], s: &mut usize) -> Result<HashMap<Bytes, Bytes>> {
+/// Parses parameters starting at a semicolon.
+///
+/// If `whitespace_separated` is set parameters may also be separated by whitespace only,
+/// otherwise everything up to the next semicolon is skipped. The names of bare flags without
+/// `=` are added to `flags`, they are stored in the map with an empty value.
+fn parse_parameters(sequence: &[u8],
+                    s: &mut usize,
+                    max_parameters: usize,
+                    whitespace_separated: bool,
+                    flags: &mut Vec<Bytes>)
+                    -> Result<HashMap<Bytes, Bytes>> {
     let mut parameters = HashMap::new();
-    'L: loop {
+    let mut count = 0;
+    loop {
+        let mut separated = false;
         'M: loop {
             if is_undefined(sequence, *s) || sequence[*s] == b';' {
                 break 'M;
@@ -132,20 +337,14 @@ fn parse_parameters(sequence: &[u8], s: &mut usize) -> Result<HashMap<Bytes, Byt
                 *s += 1;
                 continue;
             }
+            if whitespace_separated {
+                separated = true;
+                break 'M;
+            }
             if sequence[*s] == b'"' {
-                *s += 1;
-                'N: loop {
-                    if is_undefined(sequence, *s) || sequence[*s] == b'"' {
-                        if sequence[*s] == b'"' {
-                            *s += 1;
-                        }
-                        break 'N;
-                    }
-                    if sequence[*s] == b'\\' && !is_undefined(sequence, *s + 1) {
-                        *s += 1
-                    }
-                    *s += 1;
-                }
+                let mut lexer = Lexer::at(sequence, *s);
+                let _ = lexer.quoted_string();
+                *s = lexer.position();
             } else {
                 'N2: loop {
                     if is_undefined(sequence, *s) || is_whitespace(sequence[*s]) ||
@@ -159,64 +358,206 @@ fn parse_parameters(sequence: &[u8], s: &mut usize) -> Result<HashMap<Bytes, Byt
         if is_undefined(sequence, *s) {
             return Ok(parameters);
         }
-        *s += 1;
-        while is_whitespace(sequence[*s]) {
+        if !separated {
+            *s += 1;
+        }
+        while !is_undefined(sequence, *s) && is_whitespace(sequence[*s]) {
             *s += 1;
         }
+        count += 1;
+        if count > max_parameters {
+            return Err(Error::TooManyParameters);
+        }
         let mut name = Vec::new();
-        let mut extra = Vec::new();
-        let mut p = 0;
-        'M2: loop {
-            name.extend(extra.iter());
-            loop {
-                if !(is_whitespace(sequence[*s]) || sequence[*s] == b'=') {
-                    if p > 127 {
-                        return Err(Error::Invalid);
-                    }
-                    if is_undefined(sequence, *s) {
-                        if name != b"" && parameters.get(&name).is_none() {
-                            parameters.insert(name, Vec::new());
-                        }
-                        return Ok(parameters);
-                    }
-                    name.push(sequence[*s].to_ascii_lowercase());
-                    *s += 1;
-                    p += 1;
-                } else {
-                    break;
-                }
+        loop {
+            if is_undefined(sequence, *s) || is_whitespace(sequence[*s]) ||
+               sequence[*s] == b'=' || sequence[*s] == b';' {
+                break;
             }
-            loop {
-                if is_whitespace(sequence[*s]) {
-                    extra.push(sequence[*s]);
-                    *s += 1;
-                    p += 1;
-                } else {
-                    break;
-                }
+            if name.len() > 127 {
+                return Err(Error::NameTooLong);
             }
-            if sequence[*s] == b'=' {
-                break 'M2;
+            name.push(sequence[*s].to_ascii_lowercase());
+            *s += 1;
+        }
+        while !is_undefined(sequence, *s) && is_whitespace(sequence[*s]) {
+            *s += 1;
+        }
+        if is_undefined(sequence, *s) || sequence[*s] != b'=' {
+            // A bare flag without value like `foo` in `text/plain; foo; charset=utf-8`.
+            if !name.is_empty() && !parameters.contains_key(&name) {
+                flags.push(name.clone());
+                parameters.insert(name, Vec::new());
             }
+            continue;
         }
         *s += 1;
-        while is_whitespace(sequence[*s]) {
+        while !is_undefined(sequence, *s) && is_whitespace(sequence[*s]) {
             *s += 1;
         }
+        flags.retain(|flag| *flag != name);
         parameters.insert(name, parse_value(sequence, s));
     }
 }
 
-pub fn parse_media_type(sequence: &[u8]) -> Result<(Bytes, Bytes, HashMap<Bytes, Bytes>)> {
+/// Replaces each line folding (CRLF followed by spaces or tabs) with a single space.
+fn unfold(sequence: &[u8]) -> Bytes {
+    let mut unfolded = Vec::with_capacity(sequence.len());
+    let mut i = 0;
+    while i < sequence.len() {
+        if sequence[i..].starts_with(b"\r\n ") || sequence[i..].starts_with(b"\r\n\t") {
+            i += 2;
+            while i < sequence.len() && (sequence[i] == b' ' || sequence[i] == b'\t') {
+                i += 1;
+            }
+            unfolded.push(b' ');
+        } else {
+            unfolded.push(sequence[i]);
+            i += 1;
+        }
+    }
+    unfolded
+}
+
+/// Removes RFC 822 comments outside of quoted strings.
+///
+/// No whitespace is left in place of a comment so a comment inside a token like
+/// `text/(comment)plain` does not split it.
+///
+/// `comment = "(" *(ctext / quoted-pair / comment) ")"`
+fn strip_comments(sequence: &[u8]) -> Bytes {
+    let mut stripped = Vec::with_capacity(sequence.len());
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut i = 0;
+    while i < sequence.len() {
+        let c = sequence[i];
+        if depth > 0 {
+            match c {
+                b'\\' => i += 1,
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                _ => (),
+            }
+        } else if c == b'(' && !quoted {
+            depth = 1;
+        } else {
+            match c {
+                b'"' => quoted = !quoted,
+                b'\\' if quoted && i + 1 < sequence.len() => {
+                    stripped.push(c);
+                    i += 1;
+                    stripped.push(sequence[i]);
+                    i += 1;
+                    continue;
+                }
+                _ => (),
+            }
+            stripped.push(c);
+        }
+        i += 1;
+    }
+    stripped
+}
+
+/// Parses a list of parameters, the leading semicolon is optional.
+pub fn parse_parameter_list(sequence: &[u8],
+                            options: &ParseOptions)
+                            -> Result<HashMap<Bytes, Bytes>> {
+    let first = sequence.iter().cloned().find(|&c| !is_whitespace(c));
+    if first == Some(b';') {
+        return parse_parameters(sequence, &mut 0, options.max_parameters, false, &mut Vec::new());
+    }
+    let mut prefixed = Vec::with_capacity(sequence.len() + 1);
+    prefixed.push(b';');
+    prefixed.extend_from_slice(sequence);
+    parse_parameters(&prefixed, &mut 0, options.max_parameters, false, &mut Vec::new())
+}
+
+/// Removes a leading `Content-Type:` field name, other input is returned unchanged.
+fn strip_field_name(sequence: &[u8]) -> &[u8] {
+    const FIELD_NAME: &[u8] = b"content-type";
+    let mut s = 0;
+    while s < sequence.len() && is_whitespace(sequence[s]) {
+        s += 1;
+    }
+    if sequence.len() < s + FIELD_NAME.len() ||
+       !sequence[s..s + FIELD_NAME.len()].eq_ignore_ascii_case(FIELD_NAME) {
+        return sequence;
+    }
+    s += FIELD_NAME.len();
+    while s < sequence.len() && is_whitespace(sequence[s]) {
+        s += 1;
+    }
+    if sequence.get(s) == Some(&b':') {
+        &sequence[s + 1..]
+    } else {
+        sequence
+    }
+}
+
+pub fn parse_media_type(sequence: &[u8],
+                        options: &ParseOptions)
+                        -> Result<(Bytes, Bytes, RawParameters)> {
     // https://mimesniff.spec.whatwg.org/#parsing-a-mime-type
+    let mut sequence = Cow::Borrowed(sequence);
+    if options.unfold_lines {
+        sequence = Cow::Owned(unfold(&sequence));
+    }
+    if options.strip_comments {
+        sequence = Cow::Owned(strip_comments(&sequence));
+    }
+    let mut sequence = &sequence[..];
+    if options.strip_field_name {
+        sequence = strip_field_name(sequence);
+    }
     if sequence.is_empty() {
-        return Err(Error::Invalid);
+        return Err(Error::MissingSeparator);
+    }
+    let mut s: usize = 0;
+    while s < sequence.len() && is_whitespace(sequence[s]) {
+        s += 1;
     }
+    let (type_, subtype) = parse_type_portion(sequence, &mut s, options)?;
+    let whitespace_separated = options.allow_whitespace_param_separator &&
+                               !sequence[s..].contains(&b';');
+    let mut flags = Vec::new();
+    let parameters = parse_parameters(sequence, &mut s, options.max_parameters,
+                                      whitespace_separated, &mut flags)?;
+    Ok((type_, subtype, (parameters, flags)))
+}
+
+/// Parses only the type and subtype of a media type and ignores the rest.
+pub fn parse_essence(sequence: &[u8]) -> Result<(Bytes, Bytes)> {
     let mut s: usize = 0;
-    while is_whitespace(sequence[s]) {
+    while s < sequence.len() && is_whitespace(sequence[s]) {
         s += 1;
     }
-    let (type_, subtype) = try!(parse_type_portion(sequence, &mut s));
-    let parameters = try!(parse_parameters(sequence, &mut s));
-    Ok((type_, subtype, parameters))
+    parse_type_portion(sequence, &mut s, &ParseOptions::default())
+}
+
+/// Splits a comma separated header value into its elements.
+///
+/// Commas inside of quoted strings do not separate elements. Elements are trimmed and empty
+/// elements are skipped.
+pub fn split_list(s: &str) -> Vec<&str> {
+    let bytes = s.as_bytes();
+    let mut elements = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => quoted = !quoted,
+            b'\\' if quoted => i += 1,
+            b',' if !quoted => {
+                elements.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+        i += 1;
+    }
+    elements.push(&s[start..]);
+    elements.into_iter().map(|x| x.trim()).filter(|x| !x.is_empty()).collect()
 }
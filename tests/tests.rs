@@ -1,7 +1,8 @@
-#[macro_use]
 extern crate media_types;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 use media_types::*;
 
@@ -43,7 +44,7 @@ fn test_any() {
 
 #[test]
 fn test_empty() {
-    assert_eq!("".parse::<MediaType>(), Err(Error::Invalid))
+    assert_eq!("".parse::<MediaType>(), Err(Error::MissingSeparator))
 }
 
 #[test]
@@ -101,10 +102,10 @@ fn test_rfc1341_types() {
         uvztcrxeyrctzbunimnbuvzcxxrctzubinnibuvzctxrxrtczvubinbuvzctxxrcvzbuhn"
                              .parse()
                              .unwrap();
-    assert_eq!(tag.boundary(), Err(Error::Invalid));
+    assert_eq!(tag.boundary(), Err(Error::InvalidParameter("boundary".to_owned())));
 
     let tag: MediaType = "multipart/mixed; boundary=\"foo\\\"bar\"".parse().unwrap();
-    assert_eq!(tag.boundary(), Err(Error::Invalid));
+    assert_eq!(tag.boundary(), Err(Error::InvalidParameter("boundary".to_owned())));
 }
 
 #[test]
@@ -161,15 +162,1457 @@ fn test_parse_hard() {
     aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
     aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
                                                 .parse();
-    assert_eq!(tag_result, Err(Error::Invalid));
+    assert_eq!(tag_result, Err(Error::NameTooLong));
     tag_result = ("aaa").parse();
-    assert_eq!(tag_result, Err(Error::Invalid));
+    assert_eq!(tag_result, Err(Error::MissingSeparator));
     tag_result = ("text/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
     aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
     aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
     aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
                      .parse();
-    assert_eq!(tag_result, Err(Error::Invalid));
+    assert_eq!(tag_result, Err(Error::NameTooLong));
     tag_result = ("text/plain; a=b;  c = dx;foo=\"bar\"").parse();
     assert!(tag_result.is_ok());
 }
+
+#[test]
+fn test_get_param_decoded() {
+    let tag: MediaType = "text/plain; name=caf%C3%A9; raw=plain; bad=100%; odd=%zz%41; bin=%FF"
+                             .parse()
+                             .unwrap();
+    assert_eq!(tag.get_param_decoded("name").unwrap(), "café");
+    assert_eq!(tag.get_param_decoded("raw").unwrap(), "plain");
+    assert_eq!(tag.get_param_decoded("bad").unwrap(), "100%");
+    assert_eq!(tag.get_param_decoded("odd").unwrap(), "%zzA");
+    assert_eq!(tag.get_param_decoded("bin").unwrap(), "%FF");
+    let tag: MediaType = "text/plain; mixed=caf%C3%A9%FF%41; cut=%C3%A9%C3".parse().unwrap();
+    assert_eq!(tag.get_param_decoded("mixed").unwrap(), "café%FFA");
+    assert_eq!(tag.get_param_decoded("cut").unwrap(), "é%C3");
+    assert_eq!(tag.get_param_decoded("missing"), None);
+}
+
+#[test]
+fn test_media_type_list() {
+    let list: MediaTypeList = "text/html, application/xhtml+xml, , invalid, \
+                               text/plain; note=\"a, b\", */*; q=0.8"
+                                  .parse()
+                                  .unwrap();
+    assert_eq!(list.len(), 4);
+    assert_eq!(list[0], MediaType::new(Text, Standards, "html"));
+    assert_eq!(list[2].parameters.get("note").unwrap(), "a, b");
+    assert_eq!(list[1..3].len(), 2);
+    assert_eq!(list.to_string(),
+               "text/html, application/xhtml+xml, text/plain; note=\"a, b\", */*; q=0.8");
+    let mut count = 0;
+    for media_type in &list {
+        assert!(media_type.type_ != Some(Image));
+        count += 1;
+    }
+    assert_eq!(count, 4);
+    let types: Vec<MediaType> = list.into_iter().collect();
+    assert_eq!(types.len(), 4);
+}
+
+#[test]
+fn test_media_type_list_dedup() {
+    let mut list = MediaTypeList::new();
+    list.push(MediaType::new(Text, Standards, "html"));
+    list.push(MediaType::new(Image, Standards, "png"));
+    list.push(MediaType::new(Text, Standards, "html"));
+    list.dedup();
+    assert_eq!(list.to_string(), "text/html, image/png");
+}
+
+#[test]
+fn test_from_type() {
+    assert_eq!(MediaType::from(Image), MediaType::wildcard_subtype(Image));
+    let tag: MediaType = Type::Unregistered("example".into()).into();
+    assert_eq!(tag.to_string(), "example/*");
+}
+
+#[test]
+fn test_max_parameters() {
+    let mut header = "a/b".to_owned();
+    for i in 0..200 {
+        header.push_str(&format!("; x{}=y", i));
+    }
+    assert_eq!(header.parse::<MediaType>(), Err(Error::TooManyParameters));
+    let options = ParseOptions { max_parameters: 200, ..Default::default() };
+    assert_eq!(MediaType::parse_with(&header, &options).unwrap().parameters.len(),
+               200);
+    let options = ParseOptions { max_parameters: 1, ..Default::default() };
+    assert!(MediaType::parse_with("a/b; x=y", &options).is_ok());
+    assert_eq!(MediaType::parse_with("a/b; x=y; z=w", &options),
+               Err(Error::TooManyParameters));
+}
+
+#[test]
+fn test_is_wildcard() {
+    let any: MediaType = "*/*".parse().unwrap();
+    assert!(any.is_wildcard());
+    assert!(!any.is_wildcard_subtype());
+    let image: MediaType = "image/*".parse().unwrap();
+    assert!(!image.is_wildcard());
+    assert!(image.is_wildcard_subtype());
+    let png: MediaType = "image/png".parse().unwrap();
+    assert!(!png.is_wildcard());
+    assert!(!png.is_wildcard_subtype());
+}
+
+#[test]
+fn test_strip_comments() {
+    let options = ParseOptions { strip_comments: true, ..Default::default() };
+    let mut expected = MediaType::new(Text, Standards, "plain");
+    expected.parameters.insert("charset".into(), "utf-8".into());
+    for header in &["text/plain (plain text); charset=utf-8",
+                    "(leading) text/plain; charset=utf-8",
+                    "text/plain;(a (nested) comment) charset=utf-8",
+                    "text/plain; charset=utf-8(trailing)",
+                    "text/plain; charset=utf-8 (escaped \\) paren)",
+                    "text/(c)plain; charset=utf-8",
+                    "text(c)/plain; charset=utf-8",
+                    "te(c)xt/pl(c)ain; charset=utf-8",
+                    "text/plain(c);(c)charset(c)=(c)utf-8",
+                    "text/plain (c) ; charset (c) = (c) utf-8"] {
+        assert_eq!(MediaType::parse_with(header, &options), Ok(expected.clone()));
+    }
+    let tag = MediaType::parse_with("text/plain; name=\"(not a comment)\"", &options).unwrap();
+    assert_eq!(tag.parameters.get("name").unwrap(), "(not a comment)");
+    let tag: MediaType = "text/plain; charset=utf-8(trailing)".parse().unwrap();
+    assert_eq!(tag.parameters.get("charset").unwrap(), "utf-8(trailing)");
+}
+
+#[test]
+fn test_canonical_parameters() {
+    let mut first = MediaType::new(Text, Standards, "plain");
+    first.parameters.insert("format".into(), "Flowed".into());
+    first.parameters.insert("Charset".into(), "UTF-8".into());
+    first.parameters.insert("a".into(), "1".into());
+    let mut second = MediaType::new(Text, Standards, "plain");
+    second.parameters.insert("a".into(), "1".into());
+    second.parameters.insert("charset".into(), "utf-8".into());
+    second.parameters.insert("format".into(), "Flowed".into());
+    let expected: Vec<(Cow<str>, Cow<str>)> = vec![("a".into(), "1".into()),
+                                              ("charset".into(), "utf-8".into()),
+                                              ("format".into(), "Flowed".into())];
+    assert_eq!(first.canonical_parameters(), expected);
+    assert_eq!(second.canonical_parameters(), expected);
+}
+
+#[test]
+fn test_empty_tree_facets() {
+    assert_eq!("application/.foo".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    assert_eq!("application/vnd.".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    assert_eq!("application/vnd..bar".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    assert_eq!("application/vnd.foo.".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    let tag: MediaType = "application/vnd.foo.bar".parse().unwrap();
+    assert_eq!(tag.tree(), Some(&Vendor));
+    assert_eq!(tag.sub(), Some("foo.bar"));
+}
+
+#[test]
+fn test_empty_suffix_parts() {
+    for input in &["text/plain+", "application/+json", "application/+", "application/vnd.foo+"] {
+        assert_eq!(input.parse::<MediaType>(), Err(Error::InvalidSubtype), "{}", input);
+        let tokens: Vec<_> = MediaType::tokenize(input).collect();
+        assert_eq!(tokens.last(), Some(&Err(Error::InvalidSubtype)), "{}", input);
+    }
+    let tag: MediaType = "application/vnd.api+json".parse().unwrap();
+    assert_eq!(tag.suffix(), Some("json"));
+}
+
+#[test]
+fn test_matches() {
+    let png = MediaType::new(Image, Standards, "png");
+    assert!(png.matches(&MediaType::wildcard()));
+    assert!(png.matches(&MediaType::wildcard_subtype(Image)));
+    assert!(png.matches(&MediaType::new(Image, Standards, "png")));
+    assert!(!png.matches(&MediaType::new(Image, Standards, "gif")));
+    assert!(!png.matches(&MediaType::wildcard_subtype(Text)));
+
+    let patterns = [MediaType::new(Text, Standards, "html"),
+                    MediaType::wildcard_subtype(Image),
+                    MediaType::new(Image, Standards, "png"),
+                    MediaType::wildcard()];
+    assert_eq!(png.first_match(&patterns), Some(&patterns[1]));
+    let json = MediaType::new(Application, Standards, "json");
+    assert_eq!(json.first_match(&patterns), Some(&patterns[3]));
+    assert!(json.matches_any(&patterns));
+    assert!(!json.matches_any(&patterns[..3]));
+    assert_eq!(json.first_match(&patterns[..3]), None);
+}
+
+#[test]
+fn test_form_types() {
+    let form = MediaType::form_urlencoded();
+    assert_eq!(form.to_string(), "application/x-www-form-urlencoded");
+    assert_eq!("application/x-www-form-urlencoded".parse::<MediaType>(), Ok(form.clone()));
+    assert!(form.is_form_urlencoded());
+    assert!(!form.is_multipart_form_data());
+
+    let multipart = MediaType::multipart_form_data();
+    assert_eq!(multipart.to_string(), "multipart/form-data");
+    let tag: MediaType = "multipart/form-data; boundary=AaB03x".parse().unwrap();
+    assert!(tag.is_multipart_form_data());
+    assert!(!tag.is_form_urlencoded());
+    assert_eq!(tag.boundary(), Ok("AaB03x"));
+}
+
+#[test]
+fn test_subtype_with_suffix() {
+    let tag: MediaType = "image/svg+xml".parse().unwrap();
+    assert_eq!(tag.subtype_with_suffix(), "svg+xml");
+    let tag: MediaType = "text/plain".parse().unwrap();
+    assert_eq!(tag.subtype_with_suffix(), "plain");
+    let tag: MediaType = "application/vnd.foo+bar+json".parse().unwrap();
+    assert_eq!(tag.sub(), Some("foo+bar"));
+    assert_eq!(tag.subtype_with_suffix(), "foo+bar+json");
+    assert_eq!(MediaType::wildcard_subtype(Image).subtype_with_suffix(), "*");
+}
+
+#[test]
+fn test_suggested_transfer_encoding() {
+    let samples = [("text/plain", TransferEncoding::QuotedPrintable),
+                   ("text/html; charset=utf-8", TransferEncoding::QuotedPrintable),
+                   ("image/png", TransferEncoding::Base64),
+                   ("audio/ogg", TransferEncoding::Base64),
+                   ("video/mp4", TransferEncoding::Base64),
+                   ("application/octet-stream", TransferEncoding::Base64),
+                   ("multipart/mixed; boundary=foo", TransferEncoding::SevenBit),
+                   ("message/rfc822", TransferEncoding::SevenBit)];
+    for &(header, encoding) in &samples {
+        let tag: MediaType = header.parse().unwrap();
+        assert_eq!(tag.suggested_transfer_encoding(), encoding);
+    }
+}
+
+#[test]
+fn test_set_type() {
+    let mut tag: MediaType = "application/ogg".parse().unwrap();
+    tag.set_type(Audio);
+    assert_eq!(tag.to_string(), "audio/ogg");
+    assert_eq!(tag.try_set_type(Video), Ok(()));
+    assert_eq!(tag.to_string(), "video/ogg");
+
+    let mut tag: MediaType = "multipart/mixed; boundary=foo".parse().unwrap();
+    assert_eq!(tag.try_set_type(Text), Err(Error::InvalidParameter("boundary".to_owned())));
+    assert_eq!(tag.type_, Some(Multipart));
+    assert_eq!(tag.try_set_type(Multipart), Ok(()));
+}
+
+#[test]
+fn test_non_ascii_type() {
+    assert_eq!("text/pl\u{e4}in".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    assert_eq!("t\u{e9}xt/plain".parse::<MediaType>(), Err(Error::InvalidType));
+    let tag: MediaType = "text/plain; title=\"\u{e4}\"".parse().unwrap();
+    assert_eq!(tag.parameters.get("title").unwrap(), "\u{e4}");
+}
+
+#[test]
+fn test_empty_subtype() {
+    assert_eq!("text/".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    assert_eq!("text/; charset=utf-8".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    assert_eq!("text/ ".parse::<MediaType>(), Err(Error::InvalidSubtype));
+}
+
+#[test]
+fn test_type_eq_str() {
+    assert!(Image == "image");
+    assert!(Image == "IMAGE");
+    assert!(Image != "text");
+    assert!(Type::Unregistered("example".into()) == "Example");
+    assert!(Type::Unregistered("example".into()) != "examples");
+    let tag: MediaType = "image/png".parse().unwrap();
+    assert!(tag.type_.unwrap() == *"image");
+}
+
+#[test]
+fn test_display_as() {
+    let mut tag = MediaType::new_with_suffix(Type::Unregistered("Example".into()),
+                                             Tree::Unregistered("Spam".into()),
+                                             "FooBar",
+                                             "XML");
+    tag.parameters.insert("Charset".into(), "UTF-8".into());
+    tag.parameters.insert("a".into(), "Foo Bar".into());
+    assert_eq!(tag.to_string(),
+               "Example/Spam.FooBar+XML; Charset=UTF-8; a=\"Foo Bar\"");
+    assert_eq!(tag.display_as(DisplayStyle::Lowercase).to_string(),
+               "example/spam.foobar+xml; a=\"Foo Bar\"; charset=UTF-8");
+    assert_eq!(tag.display_as(DisplayStyle::Canonical).to_string(),
+               "example/spam.foobar+xml; a=\"Foo Bar\"; charset=utf-8");
+}
+
+#[test]
+fn test_retain_parameters() {
+    let mut tag: MediaType = "text/plain; charset=utf-8; format=flowed; delsp=yes"
+                                 .parse()
+                                 .unwrap();
+    tag.retain_parameters(|name, _| name == "charset");
+    assert_eq!(tag.to_string(), "text/plain; charset=utf-8");
+
+    let mut tag = MediaType::new(Text, Standards, "plain");
+    tag.parameters.insert("Secret".into(), "hunter2".into());
+    tag.parameters.insert("format".into(), "flowed".into());
+    tag.retain_parameters(|name, value| name != "secret" && value != "hunter2");
+    assert_eq!(tag.to_string(), "text/plain; format=flowed");
+}
+
+#[test]
+fn test_extend_parameters() {
+    let mut tag = MediaType::new(Text, Standards, "plain");
+    tag.extend(vec![("Charset".into(), "utf-8".into()), ("format".into(), "flowed".into())]);
+    assert_eq!(tag.to_string(), "text/plain; charset=utf-8; format=flowed");
+    let parameters: Vec<(Cow<'static, str>, Cow<'static, str>)> = vec![("delsp".into(),
+                                                                        "yes".into())];
+    tag.extend(parameters);
+    assert_eq!(tag.parameters.len(), 3);
+}
+
+#[test]
+fn test_charset_or() {
+    let tag: MediaType = "text/plain; charset=iso-8859-1".parse().unwrap();
+    assert_eq!(tag.charset_or(|| Some(Charset::Utf8)), Some(Charset::Iso88591));
+    let tag: MediaType = "text/plain".parse().unwrap();
+    assert_eq!(tag.charset_or(|| Some(Charset::Utf8)), Some(Charset::Utf8));
+    assert_eq!(tag.charset_or(|| None), None);
+}
+
+#[test]
+fn test_type_composite() {
+    for type_ in &[Text, Image, Audio, Video, Application, Model] {
+        assert!(type_.is_discrete());
+        assert!(!type_.is_composite());
+    }
+    for type_ in &[Multipart, Message] {
+        assert!(type_.is_composite());
+        assert!(!type_.is_discrete());
+    }
+    assert!(Type::Unregistered("example".into()).is_discrete());
+}
+
+#[test]
+fn test_set_suffix() {
+    let mut tag: MediaType = "application/vnd.foo+xml".parse().unwrap();
+    assert_eq!(tag.set_suffix(Some("json")), Ok(()));
+    assert_eq!(tag.to_string(), "application/vnd.foo+json");
+    assert_eq!(tag.set_suffix(Some("not a token")), Err(Error::InvalidSubtype));
+    assert_eq!(tag.suffix(), Some("json"));
+    assert_eq!(tag.set_suffix(None::<&'static str>), Ok(()));
+    assert_eq!(tag.to_string(), "application/vnd.foo");
+    let mut tag = MediaType::wildcard_subtype(Application);
+    assert_eq!(tag.set_suffix(Some("json")), Err(Error::NotFound));
+}
+
+#[test]
+fn test_unfold_lines() {
+    let header = "\r\n text/plain;\r\n charset=utf-8;\r\n\t title=\"a\r\n  b\"";
+    let options = ParseOptions { unfold_lines: true, ..Default::default() };
+    let tag = MediaType::parse_with(header, &options).unwrap();
+    assert_eq!(tag.sub(), Some("plain"));
+    assert_eq!(tag.parameters.get("charset").unwrap(), "utf-8");
+    assert_eq!(tag.parameters.get("title").unwrap(), "a b");
+    let tag: MediaType = header.parse().unwrap();
+    assert_eq!(tag.parameters.get("title").unwrap(), "a\r\n  b");
+}
+
+#[test]
+fn test_parameter_names() {
+    let tag: MediaType = "text/plain; charset=utf-8; format=flowed".parse().unwrap();
+    let mut names: Vec<&str> = tag.parameter_names().collect();
+    names.sort();
+    assert_eq!(names, ["charset", "format"]);
+    assert!(tag.has_param("CharSet"));
+    assert!(tag.has_param("format"));
+    assert!(!tag.has_param("boundary"));
+}
+
+#[test]
+fn test_try_from_tuple() {
+    assert_eq!(MediaType::try_from((Image, Standards, "png")),
+               Ok(MediaType::new(Image, Standards, "png")));
+    assert_eq!(MediaType::try_from((Image, Standards, "p ng")), Err(Error::InvalidSubtype));
+    assert_eq!(MediaType::try_from((Image, Standards, "")), Err(Error::InvalidSubtype));
+    assert_eq!(MediaType::try_from((Image, Standards, "svg", "xml")),
+               Ok(MediaType::new_with_suffix(Image, Standards, "svg", "xml")));
+    assert_eq!(MediaType::try_from((Image, Standards, "svg", "x ml")),
+               Err(Error::InvalidSubtype));
+}
+
+#[test]
+fn test_is_apache_default_text() {
+    for header in &["text/plain",
+                    "text/plain; charset=ISO-8859-1",
+                    "text/plain; charset=iso-8859-1",
+                    "text/plain; charset=UTF-8"] {
+        assert!(header.parse::<MediaType>().unwrap().is_apache_default_text());
+    }
+    for header in &["text/plain; charset=shift_jis",
+                    "text/plain; charset=utf-8",
+                    "text/plain; charset=UTF-8; format=flowed",
+                    "text/html"] {
+        assert!(!header.parse::<MediaType>().unwrap().is_apache_default_text());
+    }
+}
+
+#[test]
+fn test_to_canonical_string() {
+    let first: MediaType = "TEXT/HTML; Format=\"flowed\";Charset=\"UTF-8\"".parse().unwrap();
+    let second: MediaType = "text/html; charset=utf-8; format=flowed".parse().unwrap();
+    assert_eq!(first.to_canonical_string(), "text/html; charset=utf-8; format=flowed");
+    assert_eq!(first.to_canonical_string(), second.to_canonical_string());
+
+    let mut tag = MediaType::new(Text, Standards, "plain");
+    tag.parameters.insert("title".into(), "a \"quoted\" \\ value".into());
+    let canonical = tag.to_canonical_string();
+    assert_eq!(canonical, "text/plain; title=\"a \\\"quoted\\\" \\\\ value\"");
+    assert_eq!(tag.to_string(), canonical);
+    assert_eq!(canonical.parse::<MediaType>(), Ok(tag));
+}
+
+#[test]
+fn test_lexer() {
+    use media_types::lexer::Lexer;
+
+    let mut lexer = Lexer::new(b"utf-8;q=0.5, \"a \\\"b\\\"\"");
+    assert_eq!(lexer.token(), Ok("utf-8"));
+    assert_eq!(lexer.expect(b','), Err(Error::Invalid));
+    assert_eq!(lexer.expect(b';'), Ok(()));
+    assert_eq!(lexer.token(), Ok("q"));
+    assert_eq!(lexer.peek(), Some(b'='));
+    let mut lexer = Lexer::at(b"q=0.5, \"a \\\"b\\\"\"", 2);
+    assert_eq!(lexer.token(), Ok("0.5"));
+    assert_eq!(lexer.expect(b','), Ok(()));
+    lexer.skip_whitespace();
+    assert_eq!(lexer.token(), Err(Error::Invalid));
+    assert_eq!(lexer.quoted_string(), Ok(b"a \"b\"".to_vec()));
+    assert!(lexer.is_eof());
+    assert_eq!(Lexer::new(b"\"unterminated").quoted_string(),
+               Ok(b"unterminated".to_vec()));
+    assert_eq!(Lexer::new(b"token").quoted_string(), Err(Error::Invalid));
+}
+
+#[test]
+fn test_is_equivalent_ignoring_charset() {
+    let first: MediaType = "text/html; charset=utf-8".parse().unwrap();
+    let second: MediaType = "text/html".parse().unwrap();
+    assert!(first.is_equivalent_ignoring_charset(&second));
+    assert!(second.is_equivalent_ignoring_charset(&first));
+    let third: MediaType = "text/html; charset=utf-8; level=1".parse().unwrap();
+    assert!(!first.is_equivalent_ignoring_charset(&third));
+    let fourth: MediaType = "text/plain; charset=utf-8".parse().unwrap();
+    assert!(!first.is_equivalent_ignoring_charset(&fourth));
+}
+
+#[test]
+fn test_allow_missing_subtype() {
+    assert_eq!("image".parse::<MediaType>(), Err(Error::MissingSeparator));
+    let options = ParseOptions { allow_missing_subtype: true, ..Default::default() };
+    assert_eq!(MediaType::parse_with("image", &options),
+               Ok(MediaType::wildcard_subtype(Image)));
+    let tag = MediaType::parse_with("Image; q=0.5", &options).unwrap();
+    assert_eq!(tag.to_string(), "image/*; q=0.5");
+    assert_eq!(MediaType::parse_with("image/png", &options),
+               Ok(MediaType::new(Image, Standards, "png")));
+}
+
+#[test]
+fn test_specificity() {
+    let ranges = ["*/*", "text/*", "text/plain", "text/plain; format=flowed"];
+    for (i, range) in ranges.iter().enumerate() {
+        assert_eq!(range.parse::<MediaType>().unwrap().specificity(), i as u32);
+    }
+    let tag: MediaType = "text/plain; q=0.5".parse().unwrap();
+    assert_eq!(tag.specificity(), 2);
+    let tag: MediaType = "text/plain; format=flowed; charset=utf-8; q=1".parse().unwrap();
+    assert_eq!(tag.specificity(), 4);
+    let tag: MediaType = "text/html; level=1".parse().unwrap();
+    assert_eq!(tag.specificity(), MediaType::new(Text, Standards, "html").specificity());
+}
+
+#[test]
+fn test_empty_parameter_value() {
+    let tag: MediaType = "text/plain; charset=".parse().unwrap();
+    assert_eq!(tag.parameters.get("charset").unwrap(), "");
+    assert_eq!(tag.charset(), Err(Error::InvalidParameter("charset".to_owned())));
+    let tag: MediaType = "text/plain; charset=\"\"; format=".parse().unwrap();
+    assert_eq!(tag.charset(), Err(Error::InvalidParameter("charset".to_owned())));
+    assert_eq!(tag.parameters.get("format").unwrap(), "");
+    assert!(tag.has_param("format"));
+    let tag: MediaType = "text/plain; format= ; charset=utf-8".parse().unwrap();
+    assert_eq!(tag.parameters.get("format").unwrap(), "");
+    assert_eq!(tag.charset(), Ok(Charset::Utf8));
+    let tag: MediaType = "text/plain".parse().unwrap();
+    assert_eq!(tag.charset(), Err(Error::NotFound));
+}
+
+#[test]
+fn test_is_binary_structured() {
+    for header in &["application/vnd.foo+ber",
+                    "application/pkix-cert+der",
+                    "application/senml+cbor",
+                    "application/senml+cbor-seq",
+                    "application/vnd.wap+wbxml",
+                    "application/epub+zip",
+                    "application/vnd.foo+gzip"] {
+        assert!(header.parse::<MediaType>().unwrap().is_binary_structured());
+    }
+    for header in &["application/ld+json", "image/svg+xml", "application/cbor"] {
+        assert!(!header.parse::<MediaType>().unwrap().is_binary_structured());
+    }
+}
+
+#[test]
+fn test_nosniff_types() {
+    for header in &["application/ecmascript",
+                    "application/javascript",
+                    "application/x-ecmascript",
+                    "application/x-javascript",
+                    "text/ecmascript",
+                    "text/javascript; charset=utf-8",
+                    "text/javascript1.0",
+                    "text/javascript1.5",
+                    "text/jscript",
+                    "text/livescript",
+                    "text/x-ecmascript",
+                    "TEXT/X-JavaScript"] {
+        assert!(header.parse::<MediaType>().unwrap().is_javascript_mime_essence_match());
+    }
+    for header in &["text/plain", "application/json", "text/javascript2.0", "text/css"] {
+        assert!(!header.parse::<MediaType>().unwrap().is_javascript_mime_essence_match());
+    }
+    assert!("text/css; charset=utf-8".parse::<MediaType>().unwrap().is_css());
+    assert!(!"text/plain".parse::<MediaType>().unwrap().is_css());
+}
+
+#[test]
+fn test_charset_case_policy() {
+    let upper: MediaType = "text/plain; charset=US-ASCII".parse().unwrap();
+    let lower: MediaType = "text/plain; charset=us-ascii".parse().unwrap();
+    assert_eq!(upper.to_string(), "text/plain; charset=US-ASCII");
+    assert_eq!(lower.to_string(), "text/plain; charset=us-ascii");
+    assert_eq!(upper.charset(), Ok(Charset::UsAscii));
+    assert_eq!(lower.charset(), Ok(Charset::UsAscii));
+    assert!(upper != lower);
+    assert!(upper.semantic_eq(&lower));
+
+    let utf8: MediaType = "text/plain; charset=UTF-8".parse().unwrap();
+    assert!(!utf8.semantic_eq(&lower));
+    let flowed: MediaType = "text/plain; charset=us-ascii; format=Flowed".parse().unwrap();
+    let other: MediaType = "text/plain; charset=us-ascii; format=flowed".parse().unwrap();
+    assert!(!flowed.semantic_eq(&other));
+}
+
+#[test]
+fn test_parse_parameters_str() {
+    let mut expected = HashMap::new();
+    expected.insert("charset".into(), "utf-8".into());
+    expected.insert("boundary".into(), "foo bar".into());
+    assert_eq!(MediaType::parse_parameters_str("; charset=utf-8; boundary=\"foo bar\""),
+               Ok(expected.clone()));
+    assert_eq!(MediaType::parse_parameters_str("Charset=utf-8;boundary=\"foo bar\";"),
+               Ok(expected));
+    assert_eq!(MediaType::parse_parameters_str(""), Ok(HashMap::new()));
+    let tag: MediaType = "text/plain;".parse().unwrap();
+    assert!(tag.parameters.is_empty());
+}
+
+#[test]
+fn test_try_to_string() {
+    let mut tag = MediaType::new(Text, Standards, "plain");
+    tag.parameters.insert("charset".into(), "utf-8".into());
+    assert_eq!(tag.try_to_string(), Ok("text/plain; charset=utf-8".to_owned()));
+    tag.parameters.insert("bad name".into(), "x".into());
+    assert_eq!(tag.try_to_string(), Err(Error::Invalid));
+    tag.parameters.remove("bad name");
+    tag.parameters.insert("".into(), "x".into());
+    assert_eq!(tag.try_to_string(), Err(Error::Invalid));
+}
+
+#[test]
+fn test_registry() {
+    use media_types::registry;
+
+    let entry = registry::lookup("image/png").unwrap();
+    assert_eq!(entry.essence, "image/png");
+    assert_eq!(entry.extension(), "png");
+    assert!(!entry.is_text);
+    let entry = registry::lookup("Text/HTML").unwrap();
+    assert_eq!(entry.extensions, ["html", "htm"]);
+    assert!(entry.is_text);
+    assert_eq!(registry::lookup("application/fooblah"), None);
+
+    let tag: MediaType = "application/vnd.oasis.opendocument.text".parse().unwrap();
+    assert_eq!(tag.registry_entry().unwrap().extension(), "odt");
+    let tag: MediaType = "image/svg+xml; charset=utf-8".parse().unwrap();
+    assert_eq!(tag.registry_entry().unwrap().extension(), "svg");
+
+    let entries = registry::entries();
+    assert!(entries.windows(2).all(|pair| pair[0].essence < pair[1].essence));
+    assert!(entries.iter().all(|entry| !entry.extensions.is_empty()));
+    assert!(entries.len() >= 300);
+    for essence in &["application/rdf+xml", "audio/opus", "image/x-icon", "message/rfc822",
+                     "text/vtt", "video/x-flv", "application/yaml"] {
+        assert!(registry::lookup(essence).is_some(), "{}", essence);
+    }
+}
+
+#[test]
+fn test_essence_boxed() {
+    let first: MediaType = "Application/VND.Foo+JSON; charset=utf-8".parse().unwrap();
+    let second = MediaType::new_with_suffix(Type::Unregistered("APPLICATION".into()),
+                                            Tree::Unregistered("VND".into()),
+                                            "Foo",
+                                            "Json");
+    assert_eq!(first.essence_boxed(), second.essence_boxed());
+    assert_eq!(&*first.essence_boxed(), "application/vnd.foo+json");
+    assert_eq!(&*MediaType::wildcard().essence_boxed(), "*/*");
+}
+
+#[test]
+fn test_matches_accept_parameters() {
+    let html = MediaType::new(Text, Standards, "html");
+    let pattern: MediaType = "text/html; level=1".parse().unwrap();
+    assert!(html.matches(&pattern));
+    let pattern: MediaType = "text/*; q=0.5".parse().unwrap();
+    assert!(html.matches(&pattern));
+    let pattern: MediaType = "text/html; charset=utf-8; level=1".parse().unwrap();
+    assert!(!html.matches(&pattern));
+    let offer: MediaType = "text/html; charset=utf-8".parse().unwrap();
+    assert!(offer.matches(&pattern));
+    let offer: MediaType = "text/html; charset=iso-8859-1".parse().unwrap();
+    assert!(!offer.matches(&pattern));
+}
+
+#[test]
+fn test_parse_all() {
+    let media_types = MediaType::parse_all("text/plain, text/csv; header=\"a,b\"").unwrap();
+    assert_eq!(media_types.len(), 2);
+    assert_eq!(media_types[1].parameters.get("header").map(|x| &x[..]), Some("a,b"));
+    assert_eq!(MediaType::parse_all("text/plain, plain, image/png"), Err(Error::MissingSeparator));
+    let list: MediaTypeList = "text/plain, plain, image/png".parse().unwrap();
+    assert_eq!(list.len(), 2);
+}
+
+#[test]
+fn test_shared_media_type() {
+    let shared: SharedMediaType = "text/html; charset=utf-8".parse().unwrap();
+    let clones: Vec<SharedMediaType> = (0..100).map(|_| shared.clone()).collect();
+    for clone in &clones {
+        assert!(clone.ptr_eq(&shared));
+        assert!(std::ptr::eq(&**clone, &*shared));
+    }
+    assert_eq!(shared.to_string(), "text/html; charset=utf-8");
+    assert_eq!(shared.sub(), Some("html"));
+    let owned: MediaType = shared.into();
+    assert_eq!(clones[0], owned);
+    assert_eq!(SharedMediaType::from(owned.clone()), clones[0]);
+}
+
+#[test]
+fn test_set_parameter() {
+    let mut media_type = MediaType::new(Text, Standards, "plain");
+    assert_eq!(media_type.set_parameter("Format", "flowed"), Ok(None));
+    assert_eq!(media_type.set_parameter("format", "fixed"),
+               Ok(Some(Cow::Borrowed("flowed"))));
+    assert_eq!(media_type.set_parameter("title", "a b"), Ok(None));
+    assert_eq!(media_type.to_string(), "text/plain; format=fixed; title=\"a b\"");
+    assert_eq!(media_type.set_parameter("a b", "x"), Err(Error::Invalid));
+    assert_eq!(media_type.set_parameter("", "x"), Err(Error::Invalid));
+    assert_eq!(media_type.set_parameter("x", "a\r\nb"),
+               Err(Error::InvalidParameter("x".to_owned())));
+    assert_eq!(media_type.set_parameter("X", "a\0"),
+               Err(Error::InvalidParameter("x".to_owned())));
+    assert_eq!(media_type.parameters.len(), 2);
+}
+
+#[test]
+fn test_diff() {
+    let first: MediaType = "text/html; charset=utf-8".parse().unwrap();
+    let second: MediaType = "text/html; charset=ascii".parse().unwrap();
+    let diff = first.diff(&second);
+    assert!(!diff.type_differs && !diff.subtype_differs);
+    assert_eq!(diff.changed,
+               vec![("charset".to_owned(), "utf-8".to_owned(), "ascii".to_owned())]);
+    assert_eq!(diff.to_string(),
+               "changed parameter charset from \"utf-8\" to \"ascii\"");
+    let third: MediaType = "text/plain; format=flowed".parse().unwrap();
+    let diff = first.diff(&third);
+    assert!(diff.subtype_differs);
+    assert_eq!(diff.added, vec![("format".to_owned(), "flowed".to_owned())]);
+    assert_eq!(diff.removed, vec![("charset".to_owned(), "utf-8".to_owned())]);
+    assert!(first.diff(&first.clone()).is_empty());
+}
+
+#[test]
+fn test_lossy_values() {
+    let input = b"text/plain; title=\"caf\xe9\"; charset=utf-8";
+    assert!(MediaType::parse_bytes_with(input, &ParseOptions::default()).is_err());
+    let options = ParseOptions { lossy_values: true, ..Default::default() };
+    let media_type = MediaType::parse_bytes_with(input, &options).unwrap();
+    assert_eq!(media_type.sub(), Some("plain"));
+    assert_eq!(media_type.parameters.get("title").map(|x| &x[..]), Some("caf\u{fffd}"));
+    assert_eq!(media_type.charset(), Ok(Charset::Utf8));
+}
+
+#[test]
+fn test_is_spec_compliant_essence() {
+    let media_type: MediaType = "application/vnd.api+json".parse().unwrap();
+    assert!(media_type.is_spec_compliant_essence());
+    let media_type: MediaType = "text/-hidden".parse().unwrap();
+    assert!(!media_type.is_spec_compliant_essence());
+    let media_type: MediaType = "~text/plain".parse().unwrap();
+    assert!(!media_type.is_spec_compliant_essence());
+    assert!(!MediaType::wildcard_subtype(Image).is_spec_compliant_essence());
+    assert!(MediaType::new(Text, Standards, "a".repeat(127)).is_spec_compliant_essence());
+    assert!(!MediaType::new(Text, Standards, "a".repeat(128)).is_spec_compliant_essence());
+}
+
+#[test]
+fn test_from_str_or_default() {
+    let default = MediaType::new(Application, Standards, "octet-stream");
+    assert_eq!(MediaType::from_str_or_default("", default.clone()), default);
+    assert_eq!(MediaType::from_str_or_default("; charset=utf-8", default.clone()), default);
+    assert_eq!(MediaType::from_str_or_default("text/plain", default),
+               MediaType::new(Text, Standards, "plain"));
+}
+
+#[test]
+fn test_media_type_size() {
+    assert!(std::mem::size_of::<MediaType>() <= 144);
+    let media_type = MediaType::new(Text, Standards, "plain");
+    assert_eq!(media_type.parameters.capacity(), 0);
+    assert_eq!(media_type.clone().parameters.capacity(), 0);
+}
+
+#[test]
+fn test_type_and_tree_hash() {
+    let mut counts: HashMap<Type, usize> = HashMap::new();
+    for media_type in &["text/plain", "text/html", "image/png", "x-foo/bar"] {
+        let media_type: MediaType = media_type.parse().unwrap();
+        *counts.entry(media_type.type_.unwrap()).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&Text], 2);
+    assert_eq!(counts[&Image], 1);
+    assert_eq!(counts[&Type::Unregistered("x-foo".into())], 1);
+    let mut trees = HashMap::new();
+    trees.insert(Vendor, "vnd");
+    trees.insert(Tree::Unregistered("example".into()), "example");
+    assert_eq!(trees[&Vendor], "vnd");
+}
+
+#[test]
+fn test_parameter_count() {
+    let media_type = MediaType::new(Text, Standards, "plain");
+    assert!(!media_type.has_parameters());
+    assert_eq!(media_type.parameter_count(), 0);
+    let media_type: MediaType = "text/plain; charset=utf-8; format=flowed".parse().unwrap();
+    assert!(media_type.has_parameters());
+    assert_eq!(media_type.parameter_count(), 2);
+}
+
+#[test]
+fn test_streaming_manifests() {
+    let dash: MediaType = "application/dash+xml".parse().unwrap();
+    assert!(dash.is_dash_manifest());
+    assert!(!dash.is_hls_manifest());
+    for hls in &["application/vnd.apple.mpegurl", "application/x-mpegurl"] {
+        let hls: MediaType = hls.parse().unwrap();
+        assert!(hls.is_hls_manifest());
+        assert!(!hls.is_dash_manifest());
+    }
+    let smooth: MediaType = "application/vnd.ms-sstr+xml".parse().unwrap();
+    assert!(smooth.is_smooth_streaming());
+    assert!(!smooth.is_dash_manifest());
+    assert!(!MediaType::new(Application, Standards, "xml").is_smooth_streaming());
+}
+
+#[test]
+fn test_tokenize() {
+    use media_types::lexer::Token::*;
+    let tokens: Vec<_> = MediaType::tokenize("text/plain; charset=utf-8").collect();
+    assert_eq!(tokens,
+               vec![Ok(Type(0..4)),
+                    Ok(Separator(4..5)),
+                    Ok(Subtype(5..10)),
+                    Ok(Separator(10..11)),
+                    Ok(ParamName(12..19)),
+                    Ok(Separator(19..20)),
+                    Ok(ParamValue(20..25))]);
+    let tokens: Vec<_> = MediaType::tokenize("application/vnd.api+json; x=\"a b\"")
+                             .map(|x| x.unwrap().span())
+                             .collect();
+    assert_eq!(tokens,
+               vec![0..11, 11..12, 12..15, 15..16, 16..19, 19..20, 20..24, 24..25, 26..27,
+                    27..28, 28..33]);
+    let tokens: Vec<_> = MediaType::tokenize("text/plain; charset = utf-8").collect();
+    assert_eq!(tokens[4..],
+               [Ok(ParamName(12..19)), Ok(Separator(20..21)), Ok(ParamValue(22..27))]);
+    let tokens: Vec<_> = MediaType::tokenize("text/plain; flag; a=").collect();
+    assert_eq!(tokens[4..],
+               [Ok(ParamName(12..16)),
+                Ok(Separator(16..17)),
+                Ok(ParamName(18..19)),
+                Ok(Separator(19..20)),
+                Ok(ParamValue(20..20))]);
+    for input in &["text/plain; charset = utf-8",
+                   "text/plain; flag; a=b",
+                   "text/plain;;a= \"b c\" junk; d",
+                   "text/plain junk",
+                   "text/plain; a=b/c"] {
+        assert!(input.parse::<MediaType>().is_ok());
+        assert!(MediaType::tokenize(input).all(|token| token.is_ok()), "{}", input);
+    }
+    let tokens: Vec<_> = MediaType::tokenize("text/.plain").collect();
+    assert_eq!(tokens.last(), Some(&Err(Error::InvalidSubtype)));
+    let tokens: Vec<_> = MediaType::tokenize("text").collect();
+    assert_eq!(tokens, vec![Err(Error::MissingSeparator)]);
+}
+
+#[test]
+fn test_type_length_limits() {
+    let name = "a".repeat(127);
+    let long_name = "a".repeat(128);
+    for input in &[format!("{}/plain", name), format!("text/{}", name)] {
+        assert!(input.parse::<MediaType>().is_ok(), "{}", input);
+        assert!(MediaType::tokenize(input).all(|token| token.is_ok()), "{}", input);
+    }
+    for input in &[format!("{}/plain", long_name), format!("text/{}", long_name)] {
+        assert_eq!(input.parse::<MediaType>(), Err(Error::NameTooLong));
+        let tokens: Vec<_> = MediaType::tokenize(input).collect();
+        assert_eq!(tokens.last(), Some(&Err(Error::NameTooLong)));
+    }
+}
+
+#[test]
+fn test_matches_suffix_pattern() {
+    let vendor: MediaType = "application/vnd.foo+json".parse().unwrap();
+    let json = MediaType::new(Application, Standards, "json");
+    assert!(vendor.matches_suffix_pattern(Some(Application), "json"));
+    assert!(vendor.matches_suffix_pattern(None, "JSON"));
+    assert!(!vendor.matches_suffix_pattern(Some(Text), "json"));
+    assert!(!json.matches_suffix_pattern(Some(Application), "json"));
+    let pattern: MediaType = "application/*+json".parse().unwrap();
+    assert!(vendor.matches(&pattern));
+    assert!(!json.matches(&pattern));
+    assert!(!"application/vnd.foo+xml".parse::<MediaType>().unwrap().matches(&pattern));
+}
+
+#[test]
+fn test_normalize_charset() {
+    let mut media_type: MediaType = "text/plain; charset=iso-8859-1".parse().unwrap();
+    media_type.normalize_charset();
+    assert_eq!(media_type.to_string(), "text/plain; charset=ISO-8859-1");
+    let mut media_type: MediaType = "text/plain; charset=UTF-8".parse().unwrap();
+    media_type.normalize_charset();
+    assert_eq!(media_type.to_string(), "text/plain; charset=utf-8");
+    let mut media_type: MediaType = "text/plain; charset=x-custom".parse().unwrap();
+    media_type.normalize_charset();
+    assert_eq!(media_type.to_string(), "text/plain; charset=x-custom");
+    let mut media_type = MediaType::new(Text, Standards, "plain");
+    media_type.normalize_charset();
+    assert!(!media_type.has_parameters());
+}
+
+#[test]
+fn test_with_suffix() {
+    let media_type = MediaType::new(Application, Vendor, "foo").with_suffix("json");
+    assert_eq!(media_type.to_string(), "application/vnd.foo+json");
+    assert_eq!(media_type.with_suffix("xml").to_string(), "application/vnd.foo+xml");
+    let wildcard = MediaType::wildcard_subtype(Image).with_suffix("xml");
+    assert_eq!(wildcard, MediaType::wildcard_subtype(Image));
+}
+
+#[test]
+fn test_grammar() {
+    use media_types::grammar;
+    assert!(grammar::is_token_char('~'));
+    assert!(!grammar::is_token_char('/'));
+    assert!(grammar::is_boundary_char(' '));
+    assert!(!grammar::is_boundary_char('"'));
+    assert!(grammar::is_http_whitespace(b'\t'));
+    assert!(!grammar::is_http_whitespace(b'x'));
+    assert!(grammar::is_token("utf-8"));
+    assert!(!grammar::is_token(""));
+    assert!(grammar::is_boundary("simple boundary"));
+    assert!(!grammar::is_boundary("ends with space "));
+}
+
+#[test]
+fn test_generalize() {
+    let html: MediaType = "text/html; charset=utf-8; level=1".parse().unwrap();
+    let plain: MediaType = "text/plain; charset=utf-8".parse().unwrap();
+    let png = MediaType::new(Image, Standards, "png");
+    assert_eq!(html.generalize(&plain).to_string(), "text/*; charset=utf-8");
+    assert_eq!(html.generalize(&png), MediaType::wildcard());
+    assert_eq!(html.generalize(&html), html);
+    let other: MediaType = "text/html; charset=ascii".parse().unwrap();
+    assert_eq!(html.generalize(&other), MediaType::new(Text, Standards, "html"));
+}
+
+#[test]
+fn test_parse_accept_charset() {
+    assert_eq!(parse_accept_charset("utf-8, iso-8859-1;q=0.5, *;q=0.1").unwrap(),
+               vec![(Charset::Utf8, 1.0),
+                    (Charset::Iso88591, 0.5),
+                    (Charset::Unregistered("*".to_owned()), 0.1)]);
+    assert_eq!(parse_accept_charset("").unwrap(), vec![]);
+    assert_eq!(parse_accept_charset("utf-8; q=1.000").unwrap(), vec![(Charset::Utf8, 1.0)]);
+    assert_eq!(parse_accept_charset("utf-8;q=2"),
+               Err(Error::InvalidParameter("q".to_owned())));
+    assert_eq!(parse_accept_charset("utf-8;level=1"), Err(Error::Invalid));
+    assert_eq!(parse_accept_charset("utf-8 iso-8859-1"), Err(Error::Invalid));
+}
+
+#[test]
+fn test_type_classification() {
+    for type_ in &[Audio, Video] {
+        assert!(type_.is_audiovisual());
+    }
+    for type_ in &[Text, Image, Application, Multipart, Message, Model] {
+        assert!(!type_.is_audiovisual());
+    }
+    assert_eq!(Text.discrete_kind(), Some(DiscreteKind::Text));
+    assert_eq!(Image.discrete_kind(), Some(DiscreteKind::Image));
+    assert_eq!(Audio.discrete_kind(), Some(DiscreteKind::Audio));
+    assert_eq!(Video.discrete_kind(), Some(DiscreteKind::Video));
+    assert_eq!(Application.discrete_kind(), Some(DiscreteKind::Application));
+    assert_eq!(Model.discrete_kind(), Some(DiscreteKind::Model));
+    assert_eq!(Multipart.discrete_kind(), None);
+    assert_eq!(Message.discrete_kind(), None);
+    assert_eq!(Type::Unregistered("example".into()).discrete_kind(),
+               Some(DiscreteKind::Other));
+}
+
+#[test]
+fn test_strip_field_name() {
+    let options = ParseOptions { strip_field_name: true, ..Default::default() };
+    let expected: MediaType = "text/html; charset=utf-8".parse().unwrap();
+    for input in &["Content-Type: text/html; charset=utf-8",
+                   "content-type:text/html; charset=utf-8",
+                   "  CONTENT-TYPE : text/html; charset=utf-8",
+                   "text/html; charset=utf-8"] {
+        assert_eq!(MediaType::parse_with(input, &options), Ok(expected.clone()));
+    }
+    let media_type = MediaType::parse_with("content-typed/x; a=\"b:c\"", &options).unwrap();
+    assert_eq!(media_type.type_, Some(Type::Unregistered("content-typed".into())));
+    assert!(MediaType::parse_with("Content-Type:  ", &options).is_err());
+    assert_ne!("Content-Type: text/html".parse::<MediaType>().ok(),
+               Some(MediaType::new(Text, Standards, "html")));
+}
+
+#[test]
+fn test_rfc2231_round_trip() {
+    for input in &["example/*; codecs*=''fo%2e",
+                   "example/*; codecs*=\"''%25%20xz, gork\"",
+                   "application/x-stuff; title*=us-ascii'en-us'Thisis%20%2A%2A%2Afun%2A%2A%2A",
+                   "application/x-stuff; title*={x}'de'%E2%82%AC"] {
+        let media_type: MediaType = input.parse().unwrap();
+        assert_eq!(&media_type.to_string(), input);
+        assert_eq!(media_type.to_string().parse::<MediaType>().unwrap(), media_type);
+    }
+}
+
+#[test]
+fn test_to_owned_static() {
+    let mut media_type = MediaType::new_with_suffix(Application, Vendor, "api", "json");
+    media_type.parameters.insert("charset".into(), "utf-8".into());
+    let owned = media_type.to_owned_static();
+    assert_eq!(owned, media_type);
+    match owned.subtype {
+        Some((_, Cow::Owned(_), Some(Cow::Owned(_)))) => (),
+        _ => panic!("subtype is not owned"),
+    }
+    assert!(owned.parameters
+                 .iter()
+                 .all(|pair| matches!(pair, (&Cow::Owned(_), &Cow::Owned(_)))));
+    let handle = std::thread::spawn(move || owned.to_string());
+    assert_eq!(handle.join().unwrap(), "application/vnd.api+json; charset=utf-8");
+}
+
+#[test]
+fn test_matches_parameters() {
+    let pattern: MediaType = "text/html; charset=utf-8".parse().unwrap();
+    let offer: MediaType = "text/html; charset=UTF-8; level=2".parse().unwrap();
+    assert!(offer.matches(&pattern));
+    let offer: MediaType = "text/html; charset=iso-8859-1".parse().unwrap();
+    assert!(!offer.matches(&pattern));
+    let offer = MediaType::new(Text, Standards, "html");
+    assert!(!offer.matches(&pattern));
+    let offer: MediaType = "text/html; charset=utf-8".parse().unwrap();
+    assert!(offer.matches(&MediaType::new(Text, Standards, "html")));
+    let pattern: MediaType = "text/plain; format=Flowed".parse().unwrap();
+    let offer: MediaType = "text/plain; format=flowed".parse().unwrap();
+    assert!(!offer.matches(&pattern));
+}
+
+#[test]
+fn test_text_constructors() {
+    assert_eq!(MediaType::text("css", Charset::Utf8).to_string(), "text/css; charset=utf-8");
+    assert_eq!(MediaType::text("html", Charset::Iso88591).charset(), Ok(Charset::Iso88591));
+    assert_eq!(MediaType::text_plain_utf8(),
+               "text/plain; charset=utf-8".parse::<MediaType>().unwrap());
+}
+
+#[test]
+fn test_quoted_string_trailing_backslash() {
+    let media_type: MediaType = "text/plain; name=\"foo\\".parse().unwrap();
+    assert_eq!(media_type.parameters.get("name").map(|x| &x[..]), Some("foo\\"));
+    let media_type: MediaType = "text/plain; name=\"foo\\\"".parse().unwrap();
+    assert_eq!(media_type.parameters.get("name").map(|x| &x[..]), Some("foo\""));
+    let media_type: MediaType = "text/plain; name=\"\\".parse().unwrap();
+    assert_eq!(media_type.parameters.get("name").map(|x| &x[..]), Some("\\"));
+}
+
+#[test]
+fn test_byteranges() {
+    let media_type: MediaType = "multipart/byteranges; boundary=3d6b6a416f9b5".parse().unwrap();
+    assert!(media_type.is_byteranges());
+    assert_eq!(media_type.boundary(), Ok("3d6b6a416f9b5"));
+    assert!(!MediaType::multipart_form_data().is_byteranges());
+}
+
+#[test]
+fn test_charset_relaxed() {
+    let media_type: MediaType = "text/plain; charset=utf8".parse().unwrap();
+    assert_eq!(media_type.charset(), Ok(Charset::Unregistered("utf8".to_owned())));
+    assert_eq!(media_type.charset_relaxed(), Some(Charset::Utf8));
+    let media_type: MediaType = "text/plain; charset=Latin1".parse().unwrap();
+    assert_eq!(media_type.charset_relaxed(), Some(Charset::Iso88591));
+    let media_type: MediaType = "text/plain; charset=\"'UTF-8'\"".parse().unwrap();
+    assert_eq!(media_type.charset_relaxed(), Some(Charset::Utf8));
+    let media_type: MediaType = "text/plain; charset=klingon".parse().unwrap();
+    assert_eq!(media_type.charset_relaxed(), None);
+    assert_eq!(MediaType::new(Text, Standards, "plain").charset_relaxed(), None);
+}
+
+#[test]
+fn test_media_type_macro() {
+    assert_eq!(media_type!("text/html; charset=utf-8"),
+               MediaType::text("html", Charset::Utf8));
+    assert_eq!(media_type!("*/*"), MediaType::wildcard());
+    assert_eq!(media_type!("application/vnd.api+json; ext=\"a b\"").to_string(),
+               "application/vnd.api+json; ext=\"a b\"");
+    assert_eq!(media_type!("image/svg+xml").suffix(), Some("xml"));
+    let media_type = media_type!("text/plain; aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+        aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa=b");
+    assert_eq!(media_type.parameters.keys().next().map(|name| name.len()), Some(127));
+}
+
+#[test]
+fn test_to_bytes() {
+    let media_type: MediaType = "text/plain; charset=utf-8; title=\"a b\"".parse().unwrap();
+    assert_eq!(media_type.to_bytes(), media_type.to_string().into_bytes());
+    let mut sink = b"Content-Type: ".to_vec();
+    media_type.write_bytes(&mut sink).unwrap();
+    assert_eq!(sink, b"Content-Type: text/plain; charset=utf-8; title=\"a b\"".to_vec());
+}
+
+#[test]
+fn test_value_needs_quoting() {
+    use media_types::grammar;
+    assert!(!grammar::value_needs_quoting("charset", "utf-8"));
+    assert!(grammar::value_needs_quoting("a", "a b"));
+    assert!(grammar::value_needs_quoting("a", ""));
+    assert!(grammar::value_needs_quoting("a", "a;b"));
+    assert!(!grammar::value_needs_quoting("title*", "x{y}'en'a%20b"));
+    assert!(grammar::value_needs_quoting("title", "x{y}'en'a%20b"));
+    for &(name, value) in &[("charset", "utf-8"),
+                            ("a", "a b"),
+                            ("a", ""),
+                            ("title*", "x{y}'en'a%20b"),
+                            ("title", "x{y}'en'a%20b"),
+                            ("title*", "a b")] {
+        let mut media_type = MediaType::new(Text, Standards, "plain");
+        media_type.set_parameter(name, value).unwrap();
+        let quoted = media_type.to_string().ends_with('"');
+        assert_eq!(grammar::value_needs_quoting(name, value), quoted, "{}={}", name, value);
+    }
+    assert!(grammar::is_representable_value("a \"b\""));
+    assert!(!grammar::is_representable_value("a\r\nb"));
+}
+
+#[test]
+fn test_parse_bytes() {
+    let expected = MediaType::text("html", Charset::Utf8);
+    let bytes: Vec<u8> = b"text/html; charset=utf-8".to_vec();
+    let string = String::from("text/html; charset=utf-8");
+    assert_eq!(MediaType::parse_bytes(&bytes[..]), Ok(expected.clone()));
+    assert_eq!(MediaType::parse_bytes(bytes), Ok(expected.clone()));
+    assert_eq!(MediaType::parse_bytes(string), Ok(expected));
+    assert!(MediaType::parse_bytes(b"text/html; title=\"\xff\"").is_err());
+}
+
+#[test]
+fn test_vendor_prefix() {
+    let media_type: MediaType = "application/vnd.oasis.opendocument.text".parse().unwrap();
+    assert_eq!(media_type.vendor_prefix(), Some("oasis"));
+    let media_type: MediaType = "application/prs.alice.example+json".parse().unwrap();
+    assert_eq!(media_type.vendor_prefix(), Some("alice"));
+    let media_type: MediaType = "application/vnd.api+json".parse().unwrap();
+    assert_eq!(media_type.vendor_prefix(), None);
+    assert_eq!(MediaType::new(Text, Standards, "x.y").vendor_prefix(), None);
+}
+
+#[test]
+fn test_wildcard_subtype_parameters() {
+    let media_type: MediaType = "audio/*; q=0.5".parse().unwrap();
+    assert_eq!(media_type.type_, Some(Audio));
+    assert_eq!(media_type.subtype, None);
+    assert_eq!(media_type.parameters.get("q").map(|x| &x[..]), Some("0.5"));
+    let media_type: MediaType = "audio/*;q=0.5;codecs=opus".parse().unwrap();
+    assert_eq!(media_type.subtype, None);
+    assert_eq!(media_type.parameter_count(), 2);
+    let media_type: MediaType = "*/*; q=0.1".parse().unwrap();
+    assert!(media_type.is_wildcard());
+    assert_eq!(media_type.parameters.get("q").map(|x| &x[..]), Some("0.1"));
+}
+
+#[test]
+fn test_is_acceptable() {
+    let accept = MediaType::parse_all("text/*, text/plain; q=0, */*; q=0.1").unwrap();
+    assert!(MediaType::new(Text, Standards, "html").is_acceptable(&accept));
+    assert!(!MediaType::new(Text, Standards, "plain").is_acceptable(&accept));
+    assert!(MediaType::new(Image, Standards, "png").is_acceptable(&accept));
+    let accept = MediaType::parse_all("text/html, image/*; q=0").unwrap();
+    assert!(!MediaType::new(Image, Standards, "png").is_acceptable(&accept));
+    assert!(!MediaType::new(Audio, Standards, "ogg").is_acceptable(&accept));
+    assert_eq!(accept[1].quality(), 0.0);
+    assert_eq!(accept[0].quality(), 1.0);
+    let accept = MediaType::parse_all("text/html; level=1; q=0, text/html").unwrap();
+    assert!(MediaType::new(Text, Standards, "html").is_acceptable(&accept));
+}
+
+#[test]
+fn test_debug() {
+    let media_type = MediaType::text("html", Charset::Utf8);
+    assert_eq!(format!("{:?}", media_type), "MediaType { text/html; charset=utf-8 }");
+    assert_eq!(format!("{:?}", MediaType::wildcard()), "MediaType { */* }");
+    let pretty = format!("{:#?}", media_type);
+    assert!(pretty.contains("type_: Some("));
+    assert!(pretty.contains("\"charset\": \"utf-8\""));
+}
+
+#[test]
+fn test_charset_state() {
+    assert_eq!(MediaType::new(Text, Standards, "plain").charset_state(), CharsetState::Absent);
+    assert_eq!(MediaType::text_plain_utf8().charset_state(),
+               CharsetState::Known(Charset::Utf8));
+    let media_type: MediaType = "text/plain; charset=x-unknown".parse().unwrap();
+    assert_eq!(media_type.charset_state(), CharsetState::Unknown("x-unknown".to_owned()));
+    let media_type: MediaType = "text/plain; charset=\"\"".parse().unwrap();
+    assert_eq!(media_type.charset_state(), CharsetState::Unknown(String::new()));
+}
+
+#[test]
+fn test_subtype_facets() {
+    let media_type: MediaType = "application/vnd.oasis.opendocument.text".parse().unwrap();
+    assert_eq!(media_type.subtype_facets().collect::<Vec<_>>(),
+               vec!["oasis", "opendocument", "text"]);
+    let media_type = MediaType::new(Text, Standards, "plain");
+    assert_eq!(media_type.subtype_facets().collect::<Vec<_>>(), vec!["plain"]);
+    assert_eq!(MediaType::wildcard_subtype(Text).subtype_facets().count(), 0);
+}
+
+#[test]
+fn test_mime_matcher() {
+    let known = MediaType::parse_all("text/html, application/json, image/svg+xml").unwrap();
+    let matcher = MimeMatcher::new(&known);
+    assert_eq!(matcher.len(), 3);
+    assert!(matcher.contains_essence(&"Text/HTML; charset=utf-8".parse().unwrap()));
+    assert!(matcher.contains_essence(&"image/svg+xml".parse().unwrap()));
+    assert!(!matcher.contains_essence(&"image/svg".parse().unwrap()));
+    assert!(!matcher.contains_essence(&MediaType::wildcard()));
+    assert!(MimeMatcher::new(&[]).is_empty());
+}
+
+#[test]
+fn test_truncate_to_len() {
+    let mut media_type: MediaType =
+        "text/plain; charset=utf-8; format=flowed; delsp=yes; title=\"a long title\""
+            .parse()
+            .unwrap();
+    assert_eq!(media_type.serialized_len(), media_type.to_string().len());
+    let removed = media_type.truncate_to_len(40, &["charset", "FORMAT"]);
+    assert_eq!(removed,
+               vec![(Cow::Borrowed("title"), Cow::Borrowed("a long title")),
+                    (Cow::Borrowed("delsp"), Cow::Borrowed("yes"))]);
+    assert_eq!(media_type.to_string(), "text/plain; charset=utf-8; format=flowed");
+    let removed = media_type.truncate_to_len(10, &["charset", "format"]);
+    assert!(removed.is_empty());
+    assert_eq!(media_type.parameter_count(), 2);
+    let mut media_type: MediaType = "text/plain; charset=utf-8; zzz".parse().unwrap();
+    let removed = media_type.truncate_to_len(25, &[]);
+    assert_eq!(removed, vec![(Cow::Borrowed("zzz"), Cow::Borrowed(""))]);
+    assert_eq!(media_type.to_string(), "text/plain; charset=utf-8");
+    media_type.parameters.insert("zzz".into(), String::new().into());
+    assert_eq!(media_type.to_string(), "text/plain; charset=utf-8; zzz=\"\"");
+}
+
+#[test]
+fn test_bare_flag_parameters() {
+    let media_type: MediaType = "a/b; foo; x=y".parse().unwrap();
+    let mut expected = HashMap::new();
+    expected.insert("foo".into(), "".into());
+    expected.insert("x".into(), "y".into());
+    assert_eq!(media_type.parameters, expected);
+    assert_eq!(media_type.to_string(), "a/b; foo; x=y");
+    assert_eq!(media_type.to_string().parse::<MediaType>().unwrap(), media_type);
+    let media_type: MediaType = "text/plain; flowed ; charset=utf-8; delsp".parse().unwrap();
+    assert_eq!(media_type.to_string(), "text/plain; charset=utf-8; delsp; flowed");
+    assert_eq!(media_type.charset(), Ok(Charset::Utf8));
+    let media_type: MediaType = "a/b; empty=\"\"; foo; unquoted=".parse().unwrap();
+    assert_eq!(media_type.to_string(), "a/b; empty=\"\"; foo; unquoted=\"\"");
+    assert_eq!(media_type.to_string().parse::<MediaType>().unwrap(), media_type);
+    assert_eq!(media_type, "a/b; empty; foo; unquoted".parse().unwrap());
+    let media_type: MediaType = "a/b; x; y".parse().unwrap();
+    assert_eq!(media_type, "a/b; y; x".parse().unwrap());
+    assert_eq!(media_type.to_owned_static().to_string(), "a/b; x; y");
+    assert_eq!(media_type.display_as(DisplayStyle::Lowercase).to_string(), "a/b; x; y");
+    assert_eq!(media_type.to_canonical_string(), "a/b; x=\"\"; y=\"\"");
+    let empty: MediaType = "a/b; x=\"\"; y=".parse().unwrap();
+    assert!(media_type.semantic_eq(&empty));
+    assert_eq!(media_type.to_canonical_string(), empty.to_canonical_string());
+    let media_type: MediaType = "a/b; foo; foo=\"\"".parse().unwrap();
+    assert_eq!(media_type.to_string(), "a/b; foo=\"\"");
+    let mut media_type: MediaType = "a/b; foo".parse().unwrap();
+    media_type.set_parameter("foo", "").unwrap();
+    assert_eq!(media_type.to_string(), "a/b; foo=\"\"");
+}
+
+#[test]
+fn test_parse_first() {
+    assert_eq!(MediaType::parse_first("text/html, application/json"),
+               Ok(MediaType::new(Text, Standards, "html")));
+    let media_type = MediaType::parse_first("text/csv; header=\"a,b\", text/plain").unwrap();
+    assert_eq!(media_type.parameters.get("header").map(|x| &x[..]), Some("a,b"));
+    assert_eq!(MediaType::parse_first(" , image/png, not valid"),
+               Ok(MediaType::new(Image, Standards, "png")));
+    assert_eq!(MediaType::parse_first(""), Err(Error::MissingSeparator));
+}
+
+#[test]
+fn test_eq_ignore_ascii_case() {
+    let upper = Type::Unregistered("Foo".into());
+    let lower = Type::Unregistered("foo".into());
+    assert_ne!(upper, lower);
+    assert!(upper.eq_ignore_ascii_case(&lower));
+    assert!(!upper.eq_ignore_ascii_case(&Text));
+    let mut first = MediaType::new(upper, Tree::Unregistered("X".into()), "Bar");
+    first.parameters.insert("Charset".into(), "utf-8".into());
+    let mut second = MediaType::new(lower, Tree::Unregistered("x".into()), "bar");
+    second.parameters.insert("charset".into(), "utf-8".into());
+    assert_ne!(first, second);
+    assert!(first.eq_ignore_ascii_case(&second));
+    second.parameters.insert("charset".into(), "UTF-8".into());
+    assert!(!first.eq_ignore_ascii_case(&second));
+}
+
+#[test]
+fn test_ext_hint() {
+    use media_types::registry::MimeGroup;
+    let hint = MediaType::new(Image, Standards, "jpeg").ext_hint();
+    assert_eq!(hint.extension, Some("jpg"));
+    assert_eq!(hint.alternates, &["jpeg", "jpe"]);
+    assert_eq!(hint.group, MimeGroup::Image);
+    let hint = MediaType::new(Text, Standards, "html").ext_hint();
+    assert_eq!(hint.extension, Some("html"));
+    assert_eq!(hint.group, MimeGroup::Scriptable);
+    let hint = MediaType::new(Application, Standards, "x-unknown").ext_hint();
+    assert_eq!(hint.extension, None);
+    assert!(hint.alternates.is_empty());
+    assert_eq!(hint.group, MimeGroup::Other);
+    assert_eq!(MediaType::new(Application, Standards, "zip").mime_group(), MimeGroup::Archive);
+}
+
+#[test]
+fn test_charset_respecting_bom() {
+    let utf16 = Some(Charset::Unregistered("UTF-16LE".to_owned()));
+    let declared: MediaType = "text/html; charset=iso-8859-1".parse().unwrap();
+    let undeclared = MediaType::new(Text, Standards, "html");
+    assert_eq!(declared.charset_respecting_bom(utf16.clone()), utf16);
+    assert_eq!(declared.charset_respecting_bom(Some(Charset::Utf8)), Some(Charset::Iso88591));
+    assert_eq!(declared.charset_respecting_bom(None), Some(Charset::Iso88591));
+    assert_eq!(undeclared.charset_respecting_bom(Some(Charset::Utf8)), Some(Charset::Utf8));
+    assert_eq!(undeclared.charset_respecting_bom(None), Some(Charset::UsAscii));
+    let json = MediaType::new(Application, Standards, "json");
+    assert_eq!(json.charset_respecting_bom(None), Some(Charset::Utf8));
+    let png = MediaType::new(Image, Standards, "png");
+    assert_eq!(png.charset_respecting_bom(None), None);
+}
+
+#[test]
+fn test_dedup_by_essence() {
+    let defaults = MediaType::parse_all("text/html; q=0.5, application/json").unwrap();
+    let user = MediaType::parse_all("Text/HTML; q=0.9, image/png, application/json; q=0.1")
+                   .unwrap();
+    let mut list: MediaTypeList = defaults.into_iter().chain(user).collect();
+    assert_eq!(list.len(), 5);
+    list.dedup_by_essence();
+    assert_eq!(list.to_string(), "text/html; q=0.9, application/json, image/png");
+}
+
+#[test]
+fn test_backslash_outside_quotes() {
+    let media_type: MediaType = "a/b; x=a\\b; y=\"a\\b\"".parse().unwrap();
+    assert_eq!(media_type.parameters.get("x").map(|x| &x[..]), Some("a\\b"));
+    assert_eq!(media_type.parameters.get("y").map(|x| &x[..]), Some("ab"));
+    assert_eq!(media_type.to_string(), "a/b; x=\"a\\\\b\"; y=ab");
+    assert_eq!(media_type.to_string().parse::<MediaType>().unwrap(), media_type);
+}
+
+#[test]
+fn test_without_suffix() {
+    let svg: MediaType = "image/svg+xml".parse().unwrap();
+    assert_eq!(svg.without_suffix(), MediaType::new(Image, Standards, "svg"));
+    let vendor: MediaType = "application/vnd.foo+json; charset=utf-8".parse().unwrap();
+    assert_eq!(vendor.without_suffix().to_string(), "application/vnd.foo; charset=utf-8");
+    assert_eq!(MediaType::wildcard().without_suffix(), MediaType::wildcard());
+}
+
+#[test]
+fn test_normalized_media_type() {
+    let first: NormalizedMediaType = "Text/HTML; Charset=UTF-8".parse().unwrap();
+    let second: NormalizedMediaType = "text/html;charset=utf-8".parse().unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first.original(), "Text/HTML; Charset=UTF-8");
+    assert_eq!(second.original(), "text/html;charset=utf-8");
+    assert_eq!(first.to_string(), "Text/HTML; Charset=UTF-8");
+    assert_eq!(first.normalized(), &MediaType::text("html", Charset::Utf8));
+    let third: NormalizedMediaType = "text/html; charset=utf-8; level=1".parse().unwrap();
+    assert_ne!(first, third);
+}
+
+#[test]
+fn test_error_variants() {
+    assert_eq!("text".parse::<MediaType>(), Err(Error::MissingSeparator));
+    assert_eq!("text/vnd..x".parse::<MediaType>(), Err(Error::InvalidSubtype));
+    let long_name = format!("text/plain; {}=x", "a".repeat(200));
+    assert_eq!(long_name.parse::<MediaType>(), Err(Error::NameTooLong));
+    let tag: MediaType = "multipart/mixed; boundary=\"ends with space \"".parse().unwrap();
+    assert_eq!(tag.boundary(), Err(Error::InvalidParameter("boundary".to_owned())));
+    assert_eq!(Error::NameTooLong.to_string(), "name is too long");
+    assert_eq!(tag.boundary().unwrap_err().to_string(),
+               "value of parameter `boundary` is invalid");
+    assert_eq!("t\u{e9}xt/plain".parse::<MediaType>(), Err(Error::InvalidType));
+    assert_eq!("t\u{e9}xt/pl\u{e4}in".parse::<MediaType>(), Err(Error::InvalidType));
+    assert_eq!(" ".parse::<MediaType>(), Err(Error::MissingSeparator));
+    let mut header = "a/b".to_owned();
+    for i in 0..129 {
+        header.push_str(&format!("; x{}=y", i));
+    }
+    assert_eq!(header.parse::<MediaType>(), Err(Error::TooManyParameters));
+    let tokens: Vec<_> = MediaType::tokenize(&header).collect();
+    assert_eq!(tokens.last(), Some(&Err(Error::TooManyParameters)));
+    assert_eq!(Error::TooManyParameters.to_string(), "too many parameters");
+}
+
+#[test]
+fn test_whitespace_around_equals() {
+    let expected: MediaType = "a/b; charset=utf-8".parse().unwrap();
+    for input in &["a/b; charset = utf-8", "a/b; charset =utf-8", "a/b; charset= utf-8",
+                   "a/b;charset\t=\t\"utf-8\""] {
+        assert_eq!(input.parse::<MediaType>().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_apply_defaults() {
+    let mut json = MediaType::new(Application, Standards, "json");
+    json.apply_defaults().unwrap();
+    assert_eq!(json.charset(), Ok(Charset::Utf8));
+    let mut text: MediaType = "text/plain; charset=iso-8859-1".parse().unwrap();
+    text.apply_defaults().unwrap();
+    assert_eq!(text.charset(), Ok(Charset::Iso88591));
+    let mut png = MediaType::new(Image, Standards, "png");
+    png.apply_defaults().unwrap();
+    assert!(!png.has_parameters());
+    let mut multipart = MediaType::new(Multipart, Standards, "mixed");
+    assert_eq!(multipart.apply_defaults(), Err(Error::NotFound));
+}
+
+#[test]
+fn test_parse_essence() {
+    let media_type = MediaType::parse_essence("application/vnd.api+json; a=1; b=\"2\"").unwrap();
+    assert_eq!(media_type, MediaType::new_with_suffix(Application, Vendor, "api", "json"));
+    assert!(!media_type.has_parameters());
+    assert_eq!(MediaType::parse_essence(" text/plain;;"),
+               Ok(MediaType::new(Text, Standards, "plain")));
+    assert_eq!(MediaType::parse_essence("text"), Err(Error::MissingSeparator));
+    assert_eq!(MediaType::parse_essence("text/; charset=utf-8"), Err(Error::InvalidSubtype));
+    assert_eq!(MediaType::parse_essence("text/.plain"), Err(Error::InvalidSubtype));
+}
+
+#[test]
+fn test_tree_order() {
+    let mut trees = vec![Tree::Unregistered("foo".into()),
+                         Private,
+                         Tree::Unregistered("bar".into()),
+                         Vendor,
+                         Standards,
+                         Personal];
+    trees.sort();
+    assert_eq!(trees,
+               vec![Standards,
+                    Vendor,
+                    Personal,
+                    Private,
+                    Tree::Unregistered("bar".into()),
+                    Tree::Unregistered("foo".into())]);
+}
+
+#[test]
+fn test_boundary_length() {
+    use media_types::grammar::is_boundary;
+    assert!(is_boundary(&"a".repeat(69)));
+    assert!(is_boundary(&"a".repeat(70)));
+    assert!(!is_boundary(&"a".repeat(71)));
+    assert!(is_boundary(&format!("{} a", "a".repeat(67))));
+    assert!(!is_boundary(&format!("{} ", "a".repeat(69))));
+    assert!(!is_boundary(""));
+}
+
+#[test]
+fn test_redacted() {
+    let media_type: MediaType = "application/x.report; token=s3cr3t; charset=utf-8"
+        .parse()
+        .unwrap();
+    let redacted = media_type.redacted(&["Token", "name"]);
+    assert_eq!(redacted.parameters.get("token").map(|x| &x[..]), Some("[REDACTED]"));
+    assert_eq!(redacted.charset(), Ok(Charset::Utf8));
+    assert_eq!(redacted.parameter_count(), 2);
+    assert_eq!(media_type.parameters.get("token").map(|x| &x[..]), Some("s3cr3t"));
+}
+
+#[test]
+fn test_is_problem_details() {
+    let json: MediaType = "application/problem+json; charset=utf-8".parse().unwrap();
+    assert!(json.is_problem_details());
+    let xml: MediaType = "Application/Problem+XML".parse().unwrap();
+    assert!(xml.is_problem_details());
+    assert!(!MediaType::new(Application, Standards, "json").is_problem_details());
+    assert!(!MediaType::new(Application, Standards, "problem").is_problem_details());
+}
+
+#[test]
+fn test_is_well_known() {
+    let png: MediaType = "Image/PNG; foo=bar".parse().unwrap();
+    assert!(png.is_well_known());
+    assert!(!MediaType::new(Application, Standards, "fooblah").is_well_known());
+    assert!(!MediaType::wildcard().is_well_known());
+}
+
+#[test]
+fn test_whitespace_param_separator() {
+    let options = ParseOptions { allow_whitespace_param_separator: true, ..Default::default() };
+    let media_type = MediaType::parse_with("text/plain charset=utf-8", &options).unwrap();
+    assert_eq!(media_type.charset(), Ok(Charset::Utf8));
+    let media_type = MediaType::parse_with("text/plain  charset=utf-8 format = \"flowed\"",
+                                           &options)
+        .unwrap();
+    assert_eq!(media_type.charset(), Ok(Charset::Utf8));
+    assert_eq!(media_type.parameters.get("format").map(|x| &x[..]), Some("flowed"));
+    let media_type = MediaType::parse_with("text/plain; charset=utf-8 junk", &options).unwrap();
+    assert_eq!(media_type, "text/plain; charset=utf-8".parse().unwrap());
+    let media_type: MediaType = "text/plain charset=utf-8".parse().unwrap();
+    assert_eq!(media_type.charset(), Err(Error::NotFound));
+}